@@ -89,12 +89,21 @@ extern "C" fn sample_main() {
 #[cfg(any(target_os = "stax", target_os = "flex"))]
 fn show_status_and_home_if_needed(ins: &Instruction, tx_ctx: &mut TxContext, status: &AppSW) {
     let (return_home, show_status, status_type) = match (ins, status) {
-        (Instruction::DkgBackupKeys, AppSW::Deny | AppSW::Ok) => {
+        (Instruction::DkgBackupKeys { .. }, AppSW::Deny | AppSW::Ok) => {
             (true, false, StatusType::Operation)
         }
+        (Instruction::DkgBackupKeysCosigners { .. }, AppSW::Deny | AppSW::Ok) => {
+            (true, false, StatusType::Operation)
+        }
+        (Instruction::DkgReencryptBackup { .. }, AppSW::Deny | AppSW::Ok) if tx_ctx.done => {
+            (true, true, StatusType::Operation)
+        }
         (Instruction::DkgRestoreKeys { .. }, AppSW::Deny | AppSW::Ok) if tx_ctx.done => {
             (true, false, StatusType::Operation)
         }
+        (Instruction::DkgVerifyBackup { .. }, AppSW::Deny | AppSW::Ok) if tx_ctx.done => {
+            (true, false, StatusType::Operation)
+        }
         (Instruction::DkgGetIdentity { review: true }, AppSW::Deny | AppSW::Ok) => {
             (true, false, StatusType::Operation)
         }
@@ -107,12 +116,21 @@ fn show_status_and_home_if_needed(ins: &Instruction, tx_ctx: &mut TxContext, sta
         (Instruction::DkgRound3Min { .. }, AppSW::Deny | AppSW::Ok) if tx_ctx.done => {
             (true, true, StatusType::Operation)
         }
+        (Instruction::DkgRound3 { .. }, AppSW::Deny | AppSW::Ok) if tx_ctx.done => {
+            (true, true, StatusType::Operation)
+        }
+        (Instruction::DkgDeleteKeys { .. }, AppSW::Deny | AppSW::Ok) => {
+            (true, true, StatusType::Operation)
+        }
         (Instruction::DkgGetKeys { review: true, .. }, AppSW::Deny | AppSW::Ok) => {
             (true, false, StatusType::Address)
         }
         (Instruction::ReviewTx { .. }, AppSW::Deny | AppSW::Ok) if tx_ctx.done => {
             (true, true, StatusType::Transaction)
         }
+        (Instruction::DkgSign { .. }, AppSW::Deny | AppSW::Ok) if tx_ctx.done => {
+            (true, true, StatusType::Operation)
+        }
         (_, _) => (false, false, StatusType::Operation),
     };
 
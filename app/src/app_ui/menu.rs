@@ -15,6 +15,8 @@
  *  limitations under the License.
  *****************************************************************************/
 use alloc::string::String;
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
+use alloc::vec::Vec;
 use include_gif::include_gif;
 use ledger_device_sdk::io::Comm;
 #[cfg(not(any(target_os = "stax", target_os = "flex")))]
@@ -22,7 +24,7 @@ use ledger_device_sdk::io::Event;
 
 #[cfg(not(any(target_os = "stax", target_os = "flex")))]
 use ledger_device_sdk::ui::{
-    bitmaps::{Glyph, DASHBOARD},
+    bitmaps::{Glyph, BACK, DASHBOARD},
     gadgets::{EventOrPageIndex, MultiPageMenu, Page},
 };
 
@@ -31,7 +33,15 @@ use ledger_device_sdk::nbgl::{NbglGlyph, NbglHomeAndSettings};
 
 use crate::nvm::settings::Settings;
 #[cfg(not(any(target_os = "stax", target_os = "flex")))]
-use crate::Instruction;
+use crate::{
+    handlers::{delete_account, slot_status_label},
+    nvm::{
+        dkg_keys::{DkgKeys, DKG_KEYS_SLOTS},
+        get_and_clear_reviewed_tx,
+    },
+    utils::int_to_str,
+    Instruction,
+};
 
 #[cfg(not(any(target_os = "stax", target_os = "flex")))]
 #[inline(never)]
@@ -62,6 +72,7 @@ pub fn ui_menu_main(comm: &mut Comm) -> Event<Instruction> {
         let pages = [
             &Page::from((_first_page_label, &APP_ICON)),
             &Page::from((["Expert Mode", expert_mode_label], true, true)),
+            &Page::from((["Delete", "account"], true, true)),
             &Page::from((["Ironfish DKG", app_version.as_str()], true, true)),
             &Page::from((["Developed by", "Zondax.ch"], true, true)),
             &Page::from((["License", "Apache 2.0"], true, true)),
@@ -73,7 +84,12 @@ pub fn ui_menu_main(comm: &mut Comm) -> Event<Instruction> {
             EventOrPageIndex::Index(page_index) => {
                 match page_index {
                     1 => Settings.toggle_expert_mode(),
-                    5 => ledger_device_sdk::exit_app(0),
+                    2 => {
+                        if let Some(e) = ui_menu_delete_account(comm) {
+                            return e;
+                        }
+                    }
+                    6 => ledger_device_sdk::exit_app(0),
                     _ => (),
                 }
 
@@ -83,6 +99,57 @@ pub fn ui_menu_main(comm: &mut Comm) -> Event<Instruction> {
     }
 }
 
+/// Lists the account slots, so the user can pick the one to delete.
+/// Returns the event received meanwhile, if any, so the main menu can handle it.
+#[cfg(not(any(target_os = "stax", target_os = "flex")))]
+#[inline(never)]
+fn ui_menu_delete_account(comm: &mut Comm) -> Option<Event<Instruction>> {
+    let labels: Vec<String> = (0..DKG_KEYS_SLOTS)
+        .map(|slot| {
+            let mut label = String::from("Account ");
+            label.push_str(int_to_str(slot as u8).as_str());
+            label
+        })
+        .collect();
+
+    let mut last_page = 0;
+    loop {
+        let slots: Vec<Option<DkgKeys>> = (0..DKG_KEYS_SLOTS)
+            .map(|slot| DkgKeys::new(slot as u8).ok())
+            .collect();
+
+        let mut pages: Vec<Page> = labels
+            .iter()
+            .zip(slots.iter())
+            .map(|(label, dkg_keys)| {
+                let status = dkg_keys.as_ref().map_or("Unreadable", slot_status_label);
+                Page::from(([label.as_str(), status], true, true))
+            })
+            .collect();
+        pages.push(Page::from(("Back", &BACK)));
+
+        let pages_ref: Vec<&Page> = pages.iter().collect();
+        match MultiPageMenu::new(comm, &pages_ref).show_from(last_page) {
+            EventOrPageIndex::Event(e) => return Some(e),
+            EventOrPageIndex::Index(page_index) => {
+                let Some(Some(dkg_keys)) = slots.get(page_index) else {
+                    return None;
+                };
+
+                // Whatever was reviewed before can not be signed with a deleted account
+                if delete_account(page_index as u8, dkg_keys).is_ok() {
+                    get_and_clear_reviewed_tx();
+                }
+
+                last_page = page_index
+            }
+        }
+    }
+}
+
+// The home screen of touch devices only allows to toggle settings, and a switch can not
+// ask for confirmation before wiping an account. There is no delete account entry on them:
+// accounts are deleted through the delete account instruction, which does ask for it.
 #[cfg(any(target_os = "stax", target_os = "flex"))]
 #[inline(never)]
 pub fn ui_menu_main(_: &mut Comm) -> NbglHomeAndSettings {
@@ -17,6 +17,7 @@
 
 use crate::{ironfish::view_keys::OutgoingViewKey, ledger::zlog_stack, AppSW, Transaction};
 
+use alloc::string::String;
 use alloc::vec::Vec;
 #[cfg(not(any(target_os = "stax", target_os = "flex")))]
 use ledger_device_sdk::ui::{
@@ -36,12 +37,13 @@ use include_gif::include_gif;
 pub fn ui_review_transaction<'a>(
     transaction: &'a Transaction<'a>,
     ovk: &OutgoingViewKey,
+    from: &str,
 ) -> Result<bool, AppSW> {
     zlog_stack("ui_review_transaction***\0");
 
     #[cfg(not(any(target_os = "stax", target_os = "flex")))]
     {
-        let field_pairs = transaction.review_fields(ovk)?;
+        let field_pairs = transaction.review_fields(ovk, from)?;
 
         // Create a vector to hold the Field structs
         let fields: Vec<Field> = field_pairs
@@ -75,7 +77,7 @@ pub fn ui_review_transaction<'a>(
         #[cfg(target_os = "flex")]
         const FERRIS: NbglGlyph = NbglGlyph::from_include(include_gif!("flex_icon.gif", NBGL));
 
-        let field_pairs = transaction.review_fields(ovk)?;
+        let field_pairs = transaction.review_fields(ovk, from)?;
 
         // Create a vector to hold the Field structs
         let fields: Vec<Field> = field_pairs
@@ -195,14 +197,19 @@ pub fn ui_review_get_current_identity(i_index: u8) -> Result<bool, AppSW> {
 }
 
 #[inline(never)]
-pub fn ui_review_dkg_round1(i_index: u8, min_signers: u8, participants: u8) -> Result<bool, AppSW> {
+pub fn ui_review_dkg_round1(
+    i_index: u8,
+    min_signers: u8,
+    participants: u8,
+    session_fingerprint: &str,
+) -> Result<bool, AppSW> {
     zlog_stack("s review_dkg_round1\0");
 
     let i_index_str = int_to_str(i_index);
     let min_signers_str = int_to_str(min_signers);
     let participants_str = int_to_str(participants);
 
-    let fields: [Field; 3] = [
+    let fields: [Field; 4] = [
         Field {
             name: "Identity Num.",
             value: i_index_str.as_str(),
@@ -215,11 +222,101 @@ pub fn ui_review_dkg_round1(i_index: u8, min_signers: u8, participants: u8) -> R
             name: "Min. Signers",
             value: min_signers_str.as_str(),
         },
+        Field {
+            name: "Session",
+            value: session_fingerprint,
+        },
     ];
 
     ui_review("Round 1", "", "Accept operation?", &fields, true)
 }
 
+/// Shown before a new DKG replaces a completed account. The account shown is lost for good
+/// unless a backup of it exists.
+#[inline(never)]
+pub fn ui_review_replace_account(
+    public_address: &[u8],
+    participants: u8,
+    min_signers: u8,
+) -> Result<bool, AppSW> {
+    zlog_stack("s review_replace_account\0");
+    app_canary();
+
+    let participants_str = int_to_str(participants);
+    let min_signers_str = int_to_str(min_signers);
+    let mut public_address_hex_str = hex::encode(public_address);
+    public_address_hex_str.insert_str(0, "0x");
+
+    let fields: [Field; 4] = [
+        Field {
+            name: "Warning",
+            value: "Current account will be deleted",
+        },
+        Field {
+            name: "Public Address",
+            value: public_address_hex_str.as_str(),
+        },
+        Field {
+            name: "Participants",
+            value: participants_str.as_str(),
+        },
+        Field {
+            name: "Min. Signers",
+            value: min_signers_str.as_str(),
+        },
+    ];
+
+    ui_review("Replace Account", "", "Delete account?", &fields, true)
+}
+
+/// Shown before wiping an account slot. The public address is only known for completed accounts.
+#[inline(never)]
+pub fn ui_review_delete_account(
+    slot: u8,
+    status: &str,
+    public_address: Option<&[u8]>,
+    participants: u8,
+    min_signers: u8,
+) -> Result<bool, AppSW> {
+    zlog_stack("s review_delete_account\0");
+    app_canary();
+
+    let slot_str = int_to_str(slot);
+    let participants_str = int_to_str(participants);
+    let min_signers_str = int_to_str(min_signers);
+    let public_address_hex_str = public_address.map(|public_address| {
+        let mut value = hex::encode(public_address);
+        value.insert_str(0, "0x");
+        value
+    });
+
+    let mut fields: Vec<Field> = Vec::with_capacity(5);
+    fields.push(Field {
+        name: "Account Slot",
+        value: slot_str.as_str(),
+    });
+    fields.push(Field {
+        name: "Status",
+        value: status,
+    });
+    if let Some(public_address_hex_str) = public_address_hex_str.as_ref() {
+        fields.push(Field {
+            name: "Public Address",
+            value: public_address_hex_str.as_str(),
+        });
+        fields.push(Field {
+            name: "Participants",
+            value: participants_str.as_str(),
+        });
+        fields.push(Field {
+            name: "Min. Signers",
+            value: min_signers_str.as_str(),
+        });
+    }
+
+    ui_review("Delete Account", "", "Delete account?", &fields, true)
+}
+
 #[inline(never)]
 pub fn ui_review_dkg_round2(i_index: u8, round1_public_package_len: u8) -> Result<bool, AppSW> {
     zlog_stack("s review_dkg_round2\0");
@@ -274,13 +371,113 @@ pub fn ui_review_backup_keys(
     ui_review("Backup Keys", "", "Accept operation?", &fields, true)
 }
 
+/// Recipients are shown by their position in the DKG identities and their identity fingerprint
+#[inline(never)]
+pub fn ui_review_backup_keys_cosigners(
+    public_address: &[u8],
+    participants: u8,
+    min_signers: u8,
+    recipients: &[(u8, String)],
+) -> Result<bool, AppSW> {
+    zlog_stack("s review_backup_keys_cosigners\0");
+    app_canary();
+
+    let participants_str = int_to_str(participants);
+    let min_signers_str = int_to_str(min_signers);
+    let mut public_address_hex_str = hex::encode(public_address);
+    public_address_hex_str.insert_str(0, "0x");
+
+    let recipients_values: Vec<String> = recipients
+        .iter()
+        .map(|(index, fingerprint)| {
+            let mut value = int_to_str(*index);
+            value.insert(0, '#');
+            value.push(' ');
+            value.push_str(fingerprint);
+            value
+        })
+        .collect();
+
+    let mut fields: Vec<Field> = Vec::with_capacity(recipients_values.len() + 3);
+    fields.push(Field {
+        name: "Public Address",
+        value: public_address_hex_str.as_str(),
+    });
+    fields.push(Field {
+        name: "Participants",
+        value: participants_str.as_str(),
+    });
+    fields.push(Field {
+        name: "Min. Signers",
+        value: min_signers_str.as_str(),
+    });
+    for value in recipients_values.iter() {
+        fields.push(Field {
+            name: "Encrypt To",
+            value: value.as_str(),
+        });
+    }
+
+    ui_review(
+        "Backup To Co-signers",
+        "",
+        "Accept operation?",
+        &fields,
+        true,
+    )
+}
+
+/// Shown when a co-signer backup is handed over to a replacement device
+#[inline(never)]
+pub fn ui_review_reencrypt_backup(
+    public_address: &[u8],
+    owner: &(u8, String),
+    new_identity_fingerprint: &str,
+) -> Result<bool, AppSW> {
+    zlog_stack("s review_reencrypt_backup\0");
+    app_canary();
+
+    let mut public_address_hex_str = hex::encode(public_address);
+    public_address_hex_str.insert_str(0, "0x");
+
+    let mut owner_str = int_to_str(owner.0);
+    owner_str.insert(0, '#');
+    owner_str.push(' ');
+    owner_str.push_str(&owner.1);
+
+    let fields: [Field; 3] = [
+        Field {
+            name: "Public Address",
+            value: public_address_hex_str.as_str(),
+        },
+        Field {
+            name: "Share Of",
+            value: owner_str.as_str(),
+        },
+        Field {
+            name: "New Device",
+            value: new_identity_fingerprint,
+        },
+    ];
+
+    ui_review("Re-encrypt Backup", "", "Accept operation?", &fields, true)
+}
+
+/// The multisig account resulting from a DKG ceremony, as displayed on round 3
+pub struct DkgAccountSummary<'a> {
+    pub session_fingerprint: &'a str,
+    pub group_key_fingerprint: &'a str,
+    pub public_address: &'a [u8],
+}
+
 #[inline(never)]
 pub fn ui_review_dkg_round3(
     i_index: u8,
     round1_public_package_len: u8,
     round2_public_package_len: u8,
     participants_len: u8,
-    gsk_len: u8,
+    gsk_len: Option<u8>,
+    account: &DkgAccountSummary,
 ) -> Result<bool, AppSW> {
     zlog_stack("s review_dkg_round3\0");
     app_canary();
@@ -289,9 +486,12 @@ pub fn ui_review_dkg_round3(
     let round1_public_package_len_str = int_to_str(round1_public_package_len);
     let round2_public_package_len_str = int_to_str(round2_public_package_len);
     let participants_len_str = int_to_str(participants_len);
-    let gsk_len_str = int_to_str(gsk_len);
+    let gsk_len_str = gsk_len.map(int_to_str);
+    let mut public_address_hex_str = hex::encode(account.public_address);
+    public_address_hex_str.insert_str(0, "0x");
 
-    let fields: [Field; 5] = [
+    let mut fields: Vec<Field> = Vec::with_capacity(8);
+    fields.extend([
         Field {
             name: "Identity Num.",
             value: i_index_str.as_str(),
@@ -308,11 +508,28 @@ pub fn ui_review_dkg_round3(
             name: "Packages from R2",
             value: round2_public_package_len_str.as_str(),
         },
-        Field {
+    ]);
+    // Only sent separately on round 3 min. Otherwise, they come along with round 1 packages.
+    if let Some(gsk_len_str) = gsk_len_str.as_ref() {
+        fields.push(Field {
             name: "Group Shared Keys",
             value: gsk_len_str.as_str(),
+        });
+    }
+    fields.extend([
+        Field {
+            name: "Session",
+            value: account.session_fingerprint,
         },
-    ];
+        Field {
+            name: "Group Key",
+            value: account.group_key_fingerprint,
+        },
+        Field {
+            name: "Public Address",
+            value: public_address_hex_str.as_str(),
+        },
+    ]);
 
     ui_review("Round 3", "", "Accept operation?", &fields, true)
 }
@@ -349,6 +566,121 @@ pub fn ui_review_restore_keys(
     ui_review("Restore Keys", "", "Accept operation?", &fields, true)
 }
 
+/// Shown before a restore overwrites a different account stored in the slot
+#[inline(never)]
+pub fn ui_review_restore_replace_account(
+    current_address: &[u8],
+    restored_address: &[u8],
+) -> Result<bool, AppSW> {
+    zlog_stack("s review_restore_replace\0");
+    app_canary();
+
+    let mut current_address_hex_str = hex::encode(current_address);
+    current_address_hex_str.insert_str(0, "0x");
+    let mut restored_address_hex_str = hex::encode(restored_address);
+    restored_address_hex_str.insert_str(0, "0x");
+
+    let fields: [Field; 3] = [
+        Field {
+            name: "Warning",
+            value: "Current account will be deleted",
+        },
+        Field {
+            name: "Current Account",
+            value: current_address_hex_str.as_str(),
+        },
+        Field {
+            name: "Restored Account",
+            value: restored_address_hex_str.as_str(),
+        },
+    ];
+
+    ui_review("Replace Account", "", "Replace account?", &fields, true)
+}
+
+#[inline(never)]
+pub fn ui_review_verify_backup(
+    public_address: &[u8],
+    participants: u8,
+    min_signers: u8,
+    stored_account: &str,
+) -> Result<bool, AppSW> {
+    zlog_stack("s review_verify_backup\0");
+    app_canary();
+
+    let participants_str = int_to_str(participants);
+    let min_signers_str = int_to_str(min_signers);
+    let mut public_address_hex_str = hex::encode(public_address);
+    public_address_hex_str.insert_str(0, "0x");
+
+    let fields: [Field; 4] = [
+        Field {
+            name: "Public Address",
+            value: public_address_hex_str.as_str(),
+        },
+        Field {
+            name: "Participants",
+            value: participants_str.as_str(),
+        },
+        Field {
+            name: "Min. Signers",
+            value: min_signers_str.as_str(),
+        },
+        Field {
+            name: "Stored Account",
+            value: stored_account,
+        },
+    ];
+
+    ui_review("Verify Backup", "", "Backup is valid", &fields, true)
+}
+
+#[inline(never)]
+pub fn ui_review_dkg_sign(
+    signers: &[(u8, String)],
+    own_index: u8,
+    min_signers: u8,
+) -> Result<bool, AppSW> {
+    zlog_stack("s review_dkg_sign\0");
+    app_canary();
+
+    let signers_str = int_to_str(signers.len() as u8);
+    let min_signers_str = int_to_str(min_signers);
+
+    // Each signer is shown by its position in the DKG identities and its identity fingerprint
+    let signers_values: Vec<String> = signers
+        .iter()
+        .map(|(index, fingerprint)| {
+            let mut value = int_to_str(*index);
+            value.insert(0, '#');
+            if *index == own_index {
+                value.push_str(" (you)");
+            }
+            value.push(' ');
+            value.push_str(fingerprint);
+            value
+        })
+        .collect();
+
+    let mut fields: Vec<Field> = Vec::with_capacity(signers_values.len() + 2);
+    fields.push(Field {
+        name: "Signers",
+        value: signers_str.as_str(),
+    });
+    fields.push(Field {
+        name: "Min. Signers",
+        value: min_signers_str.as_str(),
+    });
+    for value in signers_values.iter() {
+        fields.push(Field {
+            name: "Signer",
+            value: value.as_str(),
+        });
+    }
+
+    ui_review("Sign", "", "Accept operation?", &fields, true)
+}
+
 #[inline(never)]
 pub fn ui_review<'a>(
     title: &'a str,
@@ -1,19 +1,32 @@
+pub mod backup;
 pub mod chacha20poly;
+mod dkg_session;
 mod encryption_keys;
 mod epk;
 mod keys;
 // mod ovk;
 mod guards;
+#[cfg(feature = "ledger")]
+mod signature_share;
 mod utils;
 
+#[cfg(feature = "ledger")]
+pub(crate) use dkg_session::check_dkg_session;
+pub use dkg_session::{
+    dkg_session_digest, dkg_session_fingerprint, group_key_fingerprint, public_key_package_digest,
+    DKG_SESSION_DIGEST_LEN, PUBLIC_KEY_PACKAGE_DIGEST_LEN,
+};
 pub use encryption_keys::*;
 pub use epk::Epk;
 pub use guards::GroupSecretKeyGuard;
 pub use guards::KeyPackageGuard;
+pub use guards::KeysDataGuard;
 #[cfg(feature = "ledger")]
 pub(crate) use keys::compute_dkg_secret;
 #[cfg(feature = "ledger")]
 pub(crate) use keys::derive_multisig_account;
 pub(crate) use keys::multisig_to_key_type;
 pub use keys::ConstantKey;
+#[cfg(feature = "ledger")]
+pub(crate) use signature_share::verify_randomized_signature_share;
 pub use utils::*;
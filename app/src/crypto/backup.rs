@@ -0,0 +1,383 @@
+use crate::bolos::zlog_stack;
+use crate::crypto::chacha20poly::{decrypt, encrypt, NONCE_LEN};
+use crate::crypto::guards::{EncryptionKeyGuard, KeysDataGuard};
+use crate::parser::PUBLIC_ADDRESS_SIZE;
+use crate::AppSW;
+use alloc::vec::Vec;
+use blake2b_simd::Params as Blake2b;
+#[cfg(feature = "ledger")]
+use {
+    alloc::vec,
+    core::ptr,
+    ironfish_frost::multienc::{self, MultiRecipientBlob},
+    ironfish_frost::participant::{Identity, Secret as IronfishSecret},
+    ledger_device_sdk::ecc::{bip32_derive, ChainCode, CurvesId, Secret},
+    ledger_device_sdk::random::LedgerRng,
+};
+
+// Backups start with a cleartext header, authenticated as associated data. It lets the host know
+// which account a backup belongs to. Version 1 backups only had the version byte as header,
+// and backups made before versioning have no header at all, and use the legacy key.
+pub const BACKUP_MAGIC: &[u8; 4] = b"IFBK";
+pub const BACKUP_VERSION: u8 = 2;
+const BACKUP_VERSION_1: u8 = 1;
+// Same header, but the keys are encrypted to the identities of some co-signers instead of
+// a key derived from our seed. The header is encrypted along with the keys to authenticate it.
+pub const BACKUP_VERSION_COSIGNERS: u8 = 3;
+
+pub const BACKUP_HEADER_LEN: usize = BACKUP_MAGIC.len() + 1 + PUBLIC_ADDRESS_SIZE + 2;
+
+const BACKUP_KEY_LEN: usize = 32;
+const BACKUP_KEY_PERSONALIZATION: &[u8; 16] = b"IronFishDkgBckup";
+
+#[cfg(feature = "ledger")]
+const ED25519_KEY_LEN: usize = 64;
+
+// Hardened branch reserved to the backup key. DKG identities are derived from 44'/1338'/0'/0'/x'
+#[cfg(feature = "ledger")]
+const BACKUP_KEY_BRANCH: u32 = 1;
+
+/// Cleartext header of a backup, describing the account it holds
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct BackupHeader {
+    pub version: u8,
+    pub public_address: [u8; PUBLIC_ADDRESS_SIZE],
+    pub min_signers: u8,
+    pub participants: u8,
+}
+
+impl BackupHeader {
+    /// Magic, format version, public address, min signers and participants count
+    pub fn serialize(&self) -> [u8; BACKUP_HEADER_LEN] {
+        let mut header = [0u8; BACKUP_HEADER_LEN];
+        let (magic, rest) = header.split_at_mut(BACKUP_MAGIC.len());
+        magic.copy_from_slice(BACKUP_MAGIC);
+        rest[0] = self.version;
+        rest[1..1 + PUBLIC_ADDRESS_SIZE].copy_from_slice(&self.public_address);
+        rest[1 + PUBLIC_ADDRESS_SIZE] = self.min_signers;
+        rest[2 + PUBLIC_ADDRESS_SIZE] = self.participants;
+        header
+    }
+
+    /// Reads the header of a backup. None if the backup has no header, as older ones.
+    /// Nothing is authenticated until the backup is opened.
+    pub fn parse(payload: &[u8]) -> Option<Self> {
+        let header = payload.get(..BACKUP_HEADER_LEN)?;
+        let (magic, rest) = header.split_at(BACKUP_MAGIC.len());
+        if magic != BACKUP_MAGIC || !matches!(rest[0], BACKUP_VERSION | BACKUP_VERSION_COSIGNERS) {
+            return None;
+        }
+
+        Some(BackupHeader {
+            version: rest[0],
+            public_address: rest[1..1 + PUBLIC_ADDRESS_SIZE].try_into().ok()?,
+            min_signers: rest[1 + PUBLIC_ADDRESS_SIZE],
+            participants: rest[2 + PUBLIC_ADDRESS_SIZE],
+        })
+    }
+}
+
+/// Backup encryption key, derived from the secret found on the backup branch.
+/// The personalized hash keeps it apart from any other key derived from the same seed.
+#[inline(never)]
+pub fn backup_key_from_secret(secret: &[u8]) -> EncryptionKeyGuard {
+    let hash = Blake2b::new()
+        .hash_length(BACKUP_KEY_LEN)
+        .personal(BACKUP_KEY_PERSONALIZATION)
+        .hash(secret);
+
+    EncryptionKeyGuard::from_secret_keys(hash.as_bytes())
+}
+
+#[cfg(feature = "ledger")]
+#[inline(never)]
+pub fn compute_backup_key() -> EncryptionKeyGuard {
+    let path: Vec<u32> = vec![
+        (0x80000000 | 0x2c),
+        (0x80000000 | 0x53a),
+        (0x80000000),
+        (0x80000000 | BACKUP_KEY_BRANCH),
+        (0x80000000),
+    ];
+
+    let mut secret_key = Secret::<ED25519_KEY_LEN>::new();
+    let mut cc: ChainCode = Default::default();
+
+    // Ignoring 'Result' here because known to be valid
+    let _ = bip32_derive(
+        CurvesId::Ed25519,
+        &path,
+        secret_key.as_mut(),
+        Some(cc.value.as_mut()),
+    );
+
+    let key = backup_key_from_secret(secret_key.as_ref());
+
+    // Zero out the memory of secret_key
+    unsafe {
+        ptr::write_bytes(&mut secret_key as *mut Secret<ED25519_KEY_LEN>, 0, 1);
+    }
+
+    key
+}
+
+/// Key used by backups made before versioning. It is the same key as the first half
+/// of the DKG secret for identity index 0, so it is only used to restore old backups.
+#[cfg(feature = "ledger")]
+#[inline(never)]
+pub fn compute_legacy_backup_key() -> EncryptionKeyGuard {
+    let path_0: Vec<u32> = vec![
+        (0x80000000 | 0x2c),
+        (0x80000000 | 0x53a),
+        (0x80000000),
+        (0x80000000),
+        (0x80000000),
+    ];
+
+    let mut secret_key_0 = Secret::<ED25519_KEY_LEN>::new();
+    let mut cc: ChainCode = Default::default();
+
+    // Ignoring 'Result' here because known to be valid
+    let _ = bip32_derive(
+        CurvesId::Ed25519,
+        &path_0,
+        secret_key_0.as_mut(),
+        Some(cc.value.as_mut()),
+    );
+
+    let key = EncryptionKeyGuard::from_secret_keys(secret_key_0.as_ref());
+
+    // Zero out the memory of secret_key_0
+    unsafe {
+        ptr::write_bytes(&mut secret_key_0 as *mut Secret<ED25519_KEY_LEN>, 0, 1);
+    }
+
+    key
+}
+
+/// Encrypts the keys data, prefixed by the cleartext header
+#[inline(never)]
+pub fn seal_backup(
+    key: &[u8; BACKUP_KEY_LEN],
+    header: &BackupHeader,
+    data: &[u8],
+) -> Result<Vec<u8>, AppSW> {
+    zlog_stack("start seal_backup\0");
+
+    let header = header.serialize();
+    let encrypted = encrypt(key, data, &header)?;
+
+    let mut resp = Vec::with_capacity(header.len() + encrypted.len());
+    resp.extend_from_slice(&header);
+    resp.extend_from_slice(&encrypted);
+    Ok(resp)
+}
+
+/// Decrypts a backup made by any version of the app. Versioned backups are tried first,
+/// falling back to the legacy format. As headers are authenticated, a backup can not be
+/// taken for one of another version. The header is only returned for backups that have one.
+#[inline(never)]
+pub fn open_backup(
+    payload: &[u8],
+    key: &[u8; BACKUP_KEY_LEN],
+    legacy_key: &[u8; BACKUP_KEY_LEN],
+) -> Result<(Option<BackupHeader>, KeysDataGuard), AppSW> {
+    zlog_stack("start open_backup\0");
+
+    if let Some(
+        header @ BackupHeader {
+            version: BACKUP_VERSION,
+            ..
+        },
+    ) = BackupHeader::parse(payload)
+    {
+        let (raw_header, sealed) = payload.split_at(BACKUP_HEADER_LEN);
+        if let Ok(data) = open_sealed(sealed, key, raw_header) {
+            return Ok((Some(header), data));
+        }
+    }
+
+    if let Some((&BACKUP_VERSION_1, sealed)) = payload.split_first() {
+        if let Ok(data) = open_sealed(sealed, key, &[BACKUP_VERSION_1]) {
+            return Ok((None, data));
+        }
+    }
+
+    Ok((None, open_sealed(payload, legacy_key, &[])?))
+}
+
+/// Encrypts the keys data to the given identities, so any of them can open the backup.
+/// The header must be of a co-signers backup.
+#[cfg(feature = "ledger")]
+#[inline(never)]
+pub fn seal_backup_for_identities(
+    header: &BackupHeader,
+    data: &[u8],
+    recipients: &[Identity],
+) -> Result<Vec<u8>, AppSW> {
+    zlog_stack("start seal_backup_for_identities\0");
+
+    if header.version != BACKUP_VERSION_COSIGNERS || recipients.is_empty() {
+        return Err(AppSW::EncryptionFail);
+    }
+
+    let header = header.serialize();
+
+    // The header is part of the plaintext too, as multienc has no associated data
+    let mut plaintext = Vec::with_capacity(header.len() + data.len());
+    plaintext.extend_from_slice(&header);
+    plaintext.extend_from_slice(data);
+    let plaintext = KeysDataGuard::new(plaintext);
+
+    let blob = multienc::encrypt(&plaintext, recipients, LedgerRng {}).serialize();
+
+    let mut resp = Vec::with_capacity(header.len() + blob.len());
+    resp.extend_from_slice(&header);
+    resp.extend_from_slice(&blob);
+    Ok(resp)
+}
+
+/// Decrypts a co-signers backup with the secret of one of its recipients.
+/// Returns the authenticated header along with the keys data.
+#[cfg(feature = "ledger")]
+#[inline(never)]
+pub fn open_backup_with_secret(
+    payload: &[u8],
+    secret: &IronfishSecret,
+) -> Result<(BackupHeader, KeysDataGuard), AppSW> {
+    zlog_stack("start open_backup_with_secret\0");
+
+    let header = match BackupHeader::parse(payload) {
+        Some(header) if header.version == BACKUP_VERSION_COSIGNERS => header,
+        _ => return Err(AppSW::InvalidPayload),
+    };
+
+    let blob = MultiRecipientBlob::deserialize_from(&payload[BACKUP_HEADER_LEN..])
+        .map_err(|_| AppSW::InvalidPayload)?;
+    let plaintext =
+        KeysDataGuard::new(multienc::decrypt(secret, &blob).map_err(|_| AppSW::DecryptionFail)?);
+
+    if plaintext.len() < BACKUP_HEADER_LEN
+        || plaintext[..BACKUP_HEADER_LEN] != payload[..BACKUP_HEADER_LEN]
+    {
+        return Err(AppSW::DecryptionFail);
+    }
+
+    let data = KeysDataGuard::new(plaintext[BACKUP_HEADER_LEN..].to_vec());
+
+    Ok((header, data))
+}
+
+/// Sealed data is the ciphertext, followed by the nonce
+#[inline(never)]
+fn open_sealed(
+    sealed: &[u8],
+    key: &[u8; BACKUP_KEY_LEN],
+    aad: &[u8],
+) -> Result<KeysDataGuard, AppSW> {
+    if sealed.len() < NONCE_LEN {
+        return Err(AppSW::InvalidPayload);
+    }
+
+    let (encrypted_data, nonce) = sealed.split_at(sealed.len() - NONCE_LEN);
+    decrypt(key, encrypted_data, nonce, aad)
+}
+
+#[cfg(test)]
+mod backup_test {
+    use super::*;
+
+    const KEYS_DATA: &[u8] = b"dkg keys data";
+
+    #[test]
+    fn backup_key_is_domain_separated() {
+        let secret = [7u8; 64];
+        let key = backup_key_from_secret(&secret);
+
+        assert_ne!(key[..], secret[..BACKUP_KEY_LEN]);
+        assert_eq!(key[..], backup_key_from_secret(&secret)[..]);
+    }
+
+    const HEADER: BackupHeader = BackupHeader {
+        version: BACKUP_VERSION,
+        public_address: [9u8; PUBLIC_ADDRESS_SIZE],
+        min_signers: 2,
+        participants: 3,
+    };
+
+    #[test]
+    fn versioned_backup() {
+        let (key, legacy_key) = ([1u8; 32], [2u8; 32]);
+
+        let backup = seal_backup(&key, &HEADER, KEYS_DATA)
+            .unwrap_or_else(|_| panic!("could not seal backup"));
+        assert_eq!(&backup[..BACKUP_MAGIC.len()], BACKUP_MAGIC);
+        assert_eq!(BackupHeader::parse(&backup), Some(HEADER));
+
+        let (header, data) = open_backup(&backup, &key, &legacy_key)
+            .unwrap_or_else(|_| panic!("could not open backup"));
+        assert_eq!(header, Some(HEADER));
+        assert_eq!(&data[..], KEYS_DATA);
+
+        // The header is authenticated
+        let mut tampered = backup.clone();
+        tampered[BACKUP_MAGIC.len() + 1] ^= 1;
+        assert!(BackupHeader::parse(&tampered).is_some());
+        assert!(open_backup(&tampered, &key, &legacy_key).is_err());
+
+        // Only the backup key can open it
+        assert!(open_backup(&backup, &legacy_key, &legacy_key).is_err());
+    }
+
+    #[test]
+    fn cosigners_backup_header() {
+        let header = BackupHeader {
+            version: BACKUP_VERSION_COSIGNERS,
+            ..HEADER
+        };
+
+        let mut payload = header.serialize().to_vec();
+        payload.extend_from_slice(&[0u8; 64]);
+        assert_eq!(BackupHeader::parse(&payload), Some(header));
+
+        // Not to be opened with the backup key
+        let (key, legacy_key) = ([1u8; 32], [2u8; 32]);
+        assert!(open_backup(&payload, &key, &legacy_key).is_err());
+
+        // Unknown versions have no header
+        payload[BACKUP_MAGIC.len()] = BACKUP_VERSION_COSIGNERS + 1;
+        assert_eq!(BackupHeader::parse(&payload), None);
+    }
+
+    #[test]
+    fn version_1_backup() {
+        let (key, legacy_key) = ([1u8; 32], [2u8; 32]);
+
+        let mut backup = alloc::vec![BACKUP_VERSION_1];
+        backup.extend_from_slice(
+            &encrypt(&key, KEYS_DATA, &[BACKUP_VERSION_1])
+                .unwrap_or_else(|_| panic!("could not encrypt backup")),
+        );
+        assert_eq!(BackupHeader::parse(&backup), None);
+
+        let (header, data) = open_backup(&backup, &key, &legacy_key)
+            .unwrap_or_else(|_| panic!("could not open backup"));
+        assert_eq!(header, None);
+        assert_eq!(&data[..], KEYS_DATA);
+    }
+
+    #[test]
+    fn legacy_backup() {
+        let (key, legacy_key) = ([1u8; 32], [2u8; 32]);
+
+        let backup = encrypt(&legacy_key, KEYS_DATA, &[])
+            .unwrap_or_else(|_| panic!("could not encrypt backup"));
+        let (header, data) = open_backup(&backup, &key, &legacy_key)
+            .unwrap_or_else(|_| panic!("could not open backup"));
+        assert_eq!(header, None);
+        assert_eq!(&data[..], KEYS_DATA);
+
+        // Nothing else can open it
+        assert!(open_backup(&backup, &key, &key).is_err());
+    }
+}
@@ -0,0 +1,138 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use blake2b_simd::Params as Blake2b;
+
+use crate::utils::fingerprint;
+
+#[cfg(feature = "ledger")]
+use crate::{
+    bolos::zlog_stack,
+    nvm::dkg_keys::{DkgKeys, DkgSession},
+    AppSW,
+};
+
+pub const DKG_SESSION_DIGEST_LEN: usize = 32;
+const DKG_SESSION_PERSONALIZATION: &[u8; 16] = b"IronFishDkgSessn";
+
+pub const PUBLIC_KEY_PACKAGE_DIGEST_LEN: usize = 32;
+const PUBLIC_KEY_PACKAGE_PERSONALIZATION: &[u8; 16] = b"IronFishDkgPkPkg";
+
+/// Digest binding the rounds of a DKG ceremony together. It covers the identity index
+/// used to derive our secret, min signers and the identities of all the participants.
+/// Identities are sorted first, so the order they are received in does not matter.
+#[inline(never)]
+pub fn dkg_session_digest(
+    identity_index: u8,
+    min_signers: u8,
+    mut identities: Vec<&[u8]>,
+) -> [u8; DKG_SESSION_DIGEST_LEN] {
+    identities.sort_unstable();
+
+    let mut state = Blake2b::new()
+        .hash_length(DKG_SESSION_DIGEST_LEN)
+        .personal(DKG_SESSION_PERSONALIZATION)
+        .to_state();
+
+    state.update(&[identity_index, min_signers, identities.len() as u8]);
+    for identity in identities {
+        state.update(identity);
+    }
+
+    state
+        .finalize()
+        .as_bytes()
+        .try_into()
+        .expect("hash has incorrect length")
+}
+
+/// Fingerprint of a DKG ceremony, to be compared by all the participants.
+/// Unlike the session digest, it does not depend on our identity index,
+/// so every participant gets the same one.
+#[inline(never)]
+pub fn dkg_session_fingerprint(min_signers: u8, mut identities: Vec<&[u8]>) -> String {
+    identities.sort_unstable();
+
+    let mut data: Vec<&[u8]> = Vec::with_capacity(identities.len() + 2);
+    let min_signers = [min_signers];
+    data.push(b"dkg session");
+    data.push(&min_signers);
+    data.extend(identities);
+
+    fingerprint(&data)
+}
+
+/// Fingerprint of the group public key resulting from a DKG ceremony
+#[inline(never)]
+pub fn group_key_fingerprint(group_key: &[u8]) -> String {
+    fingerprint(&[b"group key", group_key])
+}
+
+/// Digest of a serialized FROST public key package, so participants can check
+/// they all ended up with the same one without exchanging the whole package
+#[inline(never)]
+pub fn public_key_package_digest(public_key_package: &[u8]) -> [u8; PUBLIC_KEY_PACKAGE_DIGEST_LEN] {
+    Blake2b::new()
+        .hash_length(PUBLIC_KEY_PACKAGE_DIGEST_LEN)
+        .personal(PUBLIC_KEY_PACKAGE_PERSONALIZATION)
+        .hash(public_key_package)
+        .as_bytes()
+        .try_into()
+        .expect("hash has incorrect length")
+}
+
+/// Checks the inputs of a later DKG round belong to the session started on round 1
+#[cfg(feature = "ledger")]
+#[inline(never)]
+pub(crate) fn check_dkg_session(
+    dkg_keys: &DkgKeys,
+    identity_index: u8,
+    identities: Vec<&[u8]>,
+) -> Result<DkgSession, AppSW> {
+    zlog_stack("start check_dkg_session\0");
+
+    let session = dkg_keys.load_dkg_session()?;
+
+    let digest = dkg_session_digest(identity_index, session.min_signers, identities);
+    if digest != session.digest {
+        zlog_stack("dkg session mismatch\0");
+        return Err(AppSW::InvalidDkgSession);
+    }
+
+    Ok(session)
+}
+
+#[cfg(test)]
+mod dkg_session_test {
+    use super::*;
+
+    #[test]
+    fn session_digest() {
+        let (a, b, c) = ([1u8; 129], [2u8; 129], [3u8; 129]);
+
+        let digest = dkg_session_digest(0, 2, alloc::vec![&a[..], &b[..], &c[..]]);
+
+        // Order of the identities does not matter
+        assert_eq!(
+            digest,
+            dkg_session_digest(0, 2, alloc::vec![&c[..], &a[..], &b[..]])
+        );
+
+        // Anything else does
+        assert_ne!(
+            digest,
+            dkg_session_digest(1, 2, alloc::vec![&a[..], &b[..], &c[..]])
+        );
+        assert_ne!(
+            digest,
+            dkg_session_digest(0, 3, alloc::vec![&a[..], &b[..], &c[..]])
+        );
+        assert_ne!(
+            digest,
+            dkg_session_digest(0, 2, alloc::vec![&a[..], &b[..]])
+        );
+        assert_ne!(
+            digest,
+            dkg_session_digest(0, 2, alloc::vec![&a[..], &b[..], &b[..]])
+        );
+    }
+}
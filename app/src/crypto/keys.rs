@@ -18,8 +18,6 @@ use crate::bolos::zlog_stack;
 use crate::crypto::guards::IronfishSecretGuard;
 use crate::ironfish::multisig::{derive_account_keys, MultisigAccountKeys};
 #[cfg(feature = "ledger")]
-use crate::nvm::dkg_keys::DkgKeys;
-#[cfg(feature = "ledger")]
 use crate::nvm::DkgKeysReader;
 use crate::AppSW;
 use alloc::vec;
@@ -38,21 +36,11 @@ pub enum ConstantKey {
 }
 
 #[cfg(feature = "ledger")]
-pub(crate) fn derive_multisig_account(data: Option<&[u8]>) -> Result<MultisigAccountKeys, AppSW> {
+pub(crate) fn derive_multisig_account(data: &[u8]) -> Result<MultisigAccountKeys, AppSW> {
     zlog_stack("start derive_multisig_account\0");
 
-    let (group_secret_key, frost_public_key_package) = match data {
-        Some(r) => {
-            let gsk = DkgKeysReader::load_group_secret_key(r)?;
-            let frost_package = DkgKeysReader::load_frost_public_key_package(r)?;
-            (gsk, frost_package)
-        }
-        None => {
-            let gsk = DkgKeys.load_group_secret_key()?;
-            let frost_package = DkgKeys.load_frost_public_key_package()?;
-            (gsk, frost_package)
-        }
-    };
+    let group_secret_key = DkgKeysReader::load_group_secret_key(data)?;
+    let frost_public_key_package = DkgKeysReader::load_frost_public_key_package(data)?;
 
     let verifying_key: [u8; 32] = frost_public_key_package
         .verifying_key()
@@ -0,0 +1,63 @@
+use crate::bolos::zlog_stack;
+use crate::crypto::randomize_public_key;
+use crate::AppSW;
+use ironfish_frost::frost::frost_core::verify_signature_share;
+use ironfish_frost::frost::keys::{PublicKeyPackage, VerifyingShare};
+use ironfish_frost::frost::round2::SignatureShare;
+use ironfish_frost::frost::{Identifier, SigningPackage, VerifyingKey};
+
+/// Verifies the signature share of the participant `identifier`, produced under the given
+/// randomizer. Shares are created with the randomized keys, so both the participant verifying
+/// share and the group verifying key from the public package are randomized before checking it.
+#[inline(never)]
+pub(crate) fn verify_randomized_signature_share(
+    identifier: &Identifier,
+    signature_share: &SignatureShare,
+    signing_package: &SigningPackage,
+    public_key_package: &PublicKeyPackage,
+    raw_randomizer: &[u8; 32],
+) -> Result<(), AppSW> {
+    zlog_stack("start verify_randomized_signature_share\0");
+
+    let verifying_share = public_key_package
+        .verifying_shares()
+        .get(identifier)
+        .ok_or(AppSW::InvalidSignatureShare)?;
+
+    let randomized_verifying_share = randomize_key(
+        &verifying_share
+            .serialize()
+            .map_err(|_| AppSW::InvalidPublicPackage)?,
+        raw_randomizer,
+    )?;
+    let randomized_verifying_share = VerifyingShare::deserialize(&randomized_verifying_share)
+        .map_err(|_| AppSW::InvalidPublicPackage)?;
+
+    let randomized_verifying_key = randomize_key(
+        &public_key_package
+            .verifying_key()
+            .serialize()
+            .map_err(|_| AppSW::InvalidPublicPackage)?,
+        raw_randomizer,
+    )?;
+    let randomized_verifying_key = VerifyingKey::deserialize(&randomized_verifying_key)
+        .map_err(|_| AppSW::InvalidPublicPackage)?;
+
+    verify_signature_share(
+        *identifier,
+        &randomized_verifying_share,
+        signature_share,
+        signing_package,
+        &randomized_verifying_key,
+    )
+    .map_err(|_| AppSW::InvalidSignatureShare)
+}
+
+#[inline(never)]
+fn randomize_key(raw_key: &[u8], raw_randomizer: &[u8; 32]) -> Result<[u8; 32], AppSW> {
+    let raw_key: &[u8; 32] = raw_key
+        .try_into()
+        .map_err(|_| AppSW::InvalidPublicPackage)?;
+
+    randomize_public_key(raw_key, raw_randomizer).map_err(|_| AppSW::InvalidPublicPackage)
+}
@@ -2,6 +2,7 @@ use chacha20poly1305::{ChaCha20Poly1305, Key, KeyInit, Nonce};
 use jubjub::{AffinePoint, ExtendedPoint, Fq, Fr, Scalar};
 use nom::bytes::complete::take;
 
+use crate::ironfish::constants::SPENDING_KEY_GENERATOR;
 use crate::ironfish::errors::IronfishError;
 use crate::parser::ParserError;
 
@@ -15,6 +16,23 @@ pub fn parse_extended_point(raw_bytes: &[u8; 32]) -> Result<ExtendedPoint, Parse
     parse_affine_point(raw_bytes).map(ExtendedPoint::from)
 }
 
+/// Re-randomizes a spend authorizing public key (or a FROST verifying share),
+/// returning key + randomizer * SPENDING_KEY_GENERATOR encoded as bytes.
+/// This is how the transaction randomized public key is derived from the group key.
+pub fn randomize_public_key(
+    raw_key: &[u8; 32],
+    raw_randomizer: &[u8; 32],
+) -> Result<[u8; 32], ParserError> {
+    let key = parse_affine_point(raw_key)?;
+
+    let randomizer = Fr::from_bytes(raw_randomizer)
+        .into_option()
+        .ok_or(ParserError::InvalidScalar)?;
+
+    let randomized_key = SPENDING_KEY_GENERATOR * randomizer + key;
+    Ok(AffinePoint::from(randomized_key).to_bytes())
+}
+
 /// Decrypt the encrypted text using the given key and ciphertext, also checking
 /// that the mac tag is correct.
 
@@ -79,4 +97,20 @@ mod utils {
 
         assert_eq!(raw_extended, affine.to_bytes());
     }
+
+    #[test]
+    fn randomize_key() {
+        let raw_key: [u8; 32] = hex::decode(EXTENDED_POINT).unwrap().try_into().unwrap();
+
+        let randomizer = Fr::from(7u64);
+        let randomized = randomize_public_key(&raw_key, &randomizer.to_bytes()).unwrap();
+        assert_ne!(randomized, raw_key);
+
+        // Randomizing back with the opposite scalar recovers the original key
+        let restored = randomize_public_key(&randomized, &(-randomizer).to_bytes()).unwrap();
+        assert_eq!(restored, raw_key);
+
+        // Non canonical randomizers are rejected
+        assert!(randomize_public_key(&raw_key, &[0xff; 32]).is_err());
+    }
 }
@@ -1,38 +1,59 @@
 use crate::{bolos::zlog_stack, context::TxContext, AppSW, Instruction};
 use ledger_device_sdk::io::Comm;
 
+mod dkg_abort;
+mod dkg_aggregate;
 mod dkg_backup_keys;
+mod dkg_backup_keys_cosigners;
 mod dkg_commitments;
+mod dkg_delete_keys;
 mod dkg_get_identitites;
 mod dkg_get_identity;
 mod dkg_get_keys;
 mod dkg_get_public_package;
+mod dkg_get_status;
+mod dkg_list_accounts;
+mod dkg_reencrypt_backup;
 mod dkg_restore_keys;
 mod dkg_round_1;
 mod dkg_round_2;
+mod dkg_round_3;
 mod dkg_round_3_min;
 mod dkg_sign;
+mod dkg_verify_backup;
 mod get_result;
 mod get_version;
 mod review_tx;
 
 use crate::nvm::buffer::BufferMode;
-use crate::nvm::get_and_clear_tx_hash;
+use crate::nvm::get_and_clear_reviewed_tx;
+use dkg_abort::handler_dkg_abort;
+use dkg_aggregate::handler_dkg_aggregate;
 use dkg_backup_keys::handler_dkg_backup_keys;
+use dkg_backup_keys_cosigners::handler_dkg_backup_keys_cosigners;
 use dkg_commitments::handler_dkg_commitments;
+use dkg_delete_keys::handler_dkg_delete_keys;
 use dkg_get_identitites::handler_dkg_get_identities;
 use dkg_get_identity::handler_dkg_get_identity;
 use dkg_get_keys::handler_dkg_get_keys;
 use dkg_get_public_package::handler_dkg_get_public_package;
+use dkg_get_status::handler_dkg_get_status;
+use dkg_list_accounts::handler_dkg_list_accounts;
+use dkg_reencrypt_backup::handler_dkg_reencrypt_backup;
 use dkg_restore_keys::handler_dkg_restore_keys;
 use dkg_round_1::handler_dkg_round_1;
 use dkg_round_2::handler_dkg_round_2;
+use dkg_round_3::handler_dkg_round_3;
 use dkg_round_3_min::handler_dkg_round_3_min;
 use dkg_sign::handler_dkg_sign;
+use dkg_verify_backup::handler_dkg_verify_backup;
 use get_result::handler_get_result;
 use get_version::handler_get_version;
 use review_tx::handler_review_tx;
 
+pub(crate) use dkg_delete_keys::{delete_account, slot_status_label};
+pub(crate) use dkg_restore_keys::{open_any_backup, stored_account_address};
+
 pub fn handle_apdu(comm: &mut Comm, ins: &Instruction, ctx: &mut TxContext) -> Result<(), AppSW> {
     zlog_stack("handle_apdu\0");
 
@@ -48,32 +69,59 @@ pub fn handle_apdu(comm: &mut Comm, ins: &Instruction, ctx: &mut TxContext) -> R
     };
 
     // If we receive anything else than DkgSign, DkgCommitments or GetResult command
-    // reset the tx_hash ram buffer
+    // reset the reviewed tx ram buffer
     match ins {
-        Instruction::DkgSign { chunk: _chunk } => {}
-        Instruction::DkgCommitments { chunk: _chunk } => {}
+        Instruction::DkgSign { .. } => {}
+        Instruction::DkgCommitments { .. } => {}
         Instruction::GetResult { chunk: _chunk } => {}
         _ => {
-            get_and_clear_tx_hash();
+            get_and_clear_reviewed_tx();
         }
     };
 
     match ins {
         Instruction::GetVersion => handler_get_version(comm),
         Instruction::DkgGetIdentity { review } => handler_dkg_get_identity(comm, *review),
-        Instruction::DkgRound1 { chunk } => handler_dkg_round_1(comm, *chunk, ctx),
-        Instruction::DkgRound2 { chunk } => handler_dkg_round_2(comm, *chunk, ctx),
-        Instruction::DkgRound3Min { chunk } => handler_dkg_round_3_min(comm, *chunk, ctx),
-        Instruction::DkgCommitments { chunk } => handler_dkg_commitments(comm, *chunk, ctx),
-        Instruction::DkgSign { chunk } => handler_dkg_sign(comm, *chunk, ctx),
-        Instruction::DkgGetKeys { key_type, review } => {
-            handler_dkg_get_keys(comm, *review, *key_type)
+        Instruction::DkgRound1 { chunk, slot } => handler_dkg_round_1(comm, *chunk, *slot, ctx),
+        Instruction::DkgRound2 { chunk, slot } => handler_dkg_round_2(comm, *chunk, *slot, ctx),
+        Instruction::DkgRound3Min { chunk, slot } => {
+            handler_dkg_round_3_min(comm, *chunk, *slot, ctx)
+        }
+        Instruction::DkgRound3 { chunk, slot } => handler_dkg_round_3(comm, *chunk, *slot, ctx),
+        Instruction::DkgCommitments { chunk, slot } => {
+            handler_dkg_commitments(comm, *chunk, *slot, ctx)
+        }
+        Instruction::DkgSign { chunk, slot } => handler_dkg_sign(comm, *chunk, *slot, ctx),
+        Instruction::DkgGetKeys {
+            key_type,
+            review,
+            slot,
+        } => handler_dkg_get_keys(comm, *review, *key_type, *slot),
+        Instruction::DkgGetPublicPackage { slot } => {
+            handler_dkg_get_public_package(comm, *slot, ctx)
+        }
+        Instruction::DkgBackupKeys { slot } => handler_dkg_backup_keys(comm, *slot, ctx),
+        Instruction::DkgRestoreKeys { chunk, slot } => {
+            handler_dkg_restore_keys(comm, *chunk, *slot, ctx)
         }
-        Instruction::DkgGetPublicPackage => handler_dkg_get_public_package(comm, ctx),
-        Instruction::DkgBackupKeys => handler_dkg_backup_keys(comm, ctx),
-        Instruction::DkgRestoreKeys { chunk } => handler_dkg_restore_keys(comm, *chunk, ctx),
         Instruction::GetResult { chunk } => handler_get_result(comm, ctx, *chunk),
-        Instruction::ReviewTx { chunk } => handler_review_tx(comm, *chunk, ctx),
-        Instruction::DkgGetIdentities => handler_dkg_get_identities(comm, ctx),
+        Instruction::ReviewTx { chunk, slot } => handler_review_tx(comm, *chunk, *slot, ctx),
+        Instruction::DkgGetIdentities { slot } => handler_dkg_get_identities(comm, *slot, ctx),
+        Instruction::DkgAggregate { chunk, slot } => {
+            handler_dkg_aggregate(comm, *chunk, *slot, ctx)
+        }
+        Instruction::DkgListAccounts => handler_dkg_list_accounts(comm),
+        Instruction::DkgDeleteKeys { slot } => handler_dkg_delete_keys(*slot),
+        Instruction::DkgGetStatus { slot } => handler_dkg_get_status(comm, *slot),
+        Instruction::DkgAbort { slot } => handler_dkg_abort(*slot),
+        Instruction::DkgBackupKeysCosigners { slot } => {
+            handler_dkg_backup_keys_cosigners(comm, *slot, ctx)
+        }
+        Instruction::DkgReencryptBackup { chunk, slot } => {
+            handler_dkg_reencrypt_backup(comm, *chunk, *slot, ctx)
+        }
+        Instruction::DkgVerifyBackup { chunk, slot } => {
+            handler_dkg_verify_backup(comm, *chunk, *slot, ctx)
+        }
     }
 }
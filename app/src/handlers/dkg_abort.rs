@@ -0,0 +1,30 @@
+/*****************************************************************************
+ *   Ledger App Ironfish Rust.
+ *   (c) 2023 Ledger SAS.
+ *
+ *  Licensed under the Apache License, Version 2.0 (the "License");
+ *  you may not use this file except in compliance with the License.
+ *  You may obtain a copy of the License at
+ *
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *  Unless required by applicable law or agreed to in writing, software
+ *  distributed under the License is distributed on an "AS IS" BASIS,
+ *  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *  See the License for the specific language governing permissions and
+ *  limitations under the License.
+ *****************************************************************************/
+
+use crate::bolos::zlog_stack;
+use crate::nvm::dkg_keys::DkgKeys;
+use crate::AppSW;
+
+/// Drops an interrupted DKG ceremony, so the slot goes back to idle.
+/// Only the round 1 data is lost, completed accounts are never touched.
+#[inline(never)]
+pub fn handler_dkg_abort(slot: u8) -> Result<(), AppSW> {
+    zlog_stack("start handler_dkg_abort\0");
+
+    let dkg_keys = DkgKeys::new(slot)?;
+    dkg_keys.abort_dkg()
+}
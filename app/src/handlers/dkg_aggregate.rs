@@ -0,0 +1,169 @@
+/*****************************************************************************
+ *   Ledger App Ironfish Rust.
+ *   (c) 2023 Ledger SAS.
+ *
+ *  Licensed under the Apache License, Version 2.0 (the "License");
+ *  you may not use this file except in compliance with the License.
+ *  You may obtain a copy of the License at
+ *
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *  Unless required by applicable law or agreed to in writing, software
+ *  distributed under the License is distributed on an "AS IS" BASIS,
+ *  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *  See the License for the specific language governing permissions and
+ *  limitations under the License.
+ *****************************************************************************/
+use crate::accumulator::accumulate_data;
+use crate::bolos::zlog_stack;
+use crate::context::TxContext;
+use crate::crypto::verify_randomized_signature_share;
+use crate::ironfish::constants::MAX_PARTICIPANTS;
+use crate::nvm::buffer::Buffer;
+use crate::nvm::dkg_keys::DkgKeys;
+use crate::utils::response::save_result;
+use crate::AppSW;
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+use ironfish_frost::frost;
+use ironfish_frost::frost::round2::SignatureShare;
+use ironfish_frost::frost::{Identifier, RandomizedParams, Randomizer, SigningPackage};
+use ledger_device_sdk::io::Comm;
+
+pub struct Tx<'a> {
+    raw_randomizer: &'a [u8; 32],
+    randomizer: Randomizer,
+    signing_package: SigningPackage,
+    // Pairs of participant index (position in the stored identities) and its signature share
+    signature_shares: Vec<(u8, SignatureShare)>,
+}
+
+#[inline(never)]
+pub fn handler_dkg_aggregate(
+    comm: &mut Comm,
+    chunk: u8,
+    slot: u8,
+    ctx: &mut TxContext,
+) -> Result<(), AppSW> {
+    zlog_stack("start handler_dkg_aggregate\0");
+
+    let dkg_keys = DkgKeys::new(slot)?;
+
+    accumulate_data(comm, chunk, ctx)?;
+    if !ctx.done {
+        return Ok(());
+    }
+
+    let tx = parse_tx(&ctx.buffer)?;
+
+    let identities = dkg_keys.load_identities()?;
+    let public_key_package = dkg_keys.load_frost_public_key_package()?;
+
+    let mut signature_shares: BTreeMap<Identifier, SignatureShare> = BTreeMap::new();
+    for (index, signature_share) in tx.signature_shares {
+        let identifier = identities
+            .get(index as usize)
+            .ok_or(AppSW::InvalidIdentityIndex)?
+            .to_frost_identifier();
+
+        // Each share is verified on its own, so we can tell which participant sent a faulty one
+        if verify_randomized_signature_share(
+            &identifier,
+            &signature_share,
+            &tx.signing_package,
+            &public_key_package,
+            tx.raw_randomizer,
+        )
+        .is_err()
+        {
+            zlog_stack("invalid participant share\0");
+            comm.append(&[index]);
+            return Err(AppSW::InvalidParticipantShare);
+        }
+
+        if signature_shares
+            .insert(identifier, signature_share)
+            .is_some()
+        {
+            return Err(AppSW::InvalidPayload);
+        }
+    }
+
+    let randomized_params =
+        RandomizedParams::from_randomizer(public_key_package.verifying_key(), tx.randomizer);
+
+    zlog_stack("start aggregate\0");
+    let signature = frost::aggregate(
+        &tx.signing_package,
+        &signature_shares,
+        &public_key_package,
+        &randomized_params,
+    )
+    .map_err(|_| AppSW::TxSignFail)?;
+
+    let resp = signature.serialize().map_err(|_| AppSW::TxSignFail)?;
+
+    let total_chunks = save_result(ctx, resp.as_slice())?;
+    comm.append(&total_chunks);
+    Ok(())
+}
+
+#[inline(never)]
+fn parse_tx(buffer: &Buffer) -> Result<Tx, AppSW> {
+    zlog_stack("start parse_tx\0");
+
+    let mut tx_pos = 0;
+
+    let pk_randomness_len = buffer.get_u16(tx_pos)?;
+    tx_pos += 2;
+
+    let raw_randomizer: &[u8; 32] = buffer
+        .get_slice(tx_pos, tx_pos + pk_randomness_len)?
+        .try_into()
+        .map_err(|_| AppSW::InvalidRandomizer)?;
+    let randomizer =
+        Randomizer::deserialize(raw_randomizer).map_err(|_| AppSW::InvalidRandomizer)?;
+    tx_pos += pk_randomness_len;
+
+    let frost_signing_package_len = buffer.get_u16(tx_pos)?;
+    tx_pos += 2;
+
+    let data = buffer.get_slice(tx_pos, tx_pos + frost_signing_package_len)?;
+    let signing_package =
+        SigningPackage::deserialize(data).map_err(|_| AppSW::InvalidSigningPackage)?;
+    tx_pos += frost_signing_package_len;
+
+    let elements = buffer.get_element(tx_pos)?;
+    tx_pos += 1;
+
+    if elements > MAX_PARTICIPANTS {
+        return Err(AppSW::TooManyParticipants);
+    }
+
+    let mut signature_shares = Vec::with_capacity(elements as usize);
+    for _i in 0..elements {
+        let index = buffer.get_element(tx_pos)?;
+        tx_pos += 1;
+
+        let signature_share_len = buffer.get_u16(tx_pos)?;
+        tx_pos += 2;
+
+        let data = buffer.get_slice(tx_pos, tx_pos + signature_share_len)?;
+        let signature_share =
+            SignatureShare::deserialize(data).map_err(|_| AppSW::InvalidSignatureShare)?;
+        tx_pos += signature_share_len;
+
+        signature_shares.push((index, signature_share));
+    }
+
+    if tx_pos != buffer.pos {
+        return Err(AppSW::InvalidPayload);
+    }
+
+    Ok(Tx {
+        raw_randomizer,
+        randomizer,
+        signing_package,
+        signature_shares,
+    })
+}
@@ -17,7 +17,7 @@
 use crate::app_ui::run_action::ui_review_backup_keys;
 use crate::bolos::zlog;
 use crate::context::TxContext;
-use crate::crypto::chacha20poly::{compute_key, encrypt};
+use crate::crypto::backup::{compute_backup_key, seal_backup, BackupHeader, BACKUP_VERSION};
 use crate::crypto::{derive_multisig_account, multisig_to_key_type};
 use crate::nvm::dkg_keys::DkgKeys;
 use crate::utils::response::save_result;
@@ -25,24 +25,41 @@ use crate::AppSW;
 use ledger_device_sdk::io::Comm;
 
 #[inline(never)]
-pub fn handler_dkg_backup_keys(comm: &mut Comm, ctx: &mut TxContext) -> Result<(), AppSW> {
+pub fn handler_dkg_backup_keys(
+    comm: &mut Comm,
+    slot: u8,
+    ctx: &mut TxContext,
+) -> Result<(), AppSW> {
     zlog("start handler_dkg_backup_keys\0");
 
-    let account_keys = derive_multisig_account(None)?;
+    let dkg_keys = DkgKeys::new(slot)?;
+
+    let account_keys = derive_multisig_account(dkg_keys.data())?;
     let public_address = multisig_to_key_type(&account_keys, 0u8)?;
     drop(account_keys);
 
-    let min_signers = DkgKeys.load_min_signers()?;
-    let participants = DkgKeys.load_identities()?.len();
+    let min_signers = dkg_keys.load_min_signers()?;
+    let participants = dkg_keys.load_identities()?.len();
+
+    // The header lets the host know which account the backup belongs to
+    let header = BackupHeader {
+        version: BACKUP_VERSION,
+        public_address: public_address
+            .as_slice()
+            .try_into()
+            .map_err(|_| AppSW::KeyDeriveFail)?,
+        min_signers: min_signers as u8,
+        participants: participants as u8,
+    };
 
     if !ui_review_backup_keys(public_address, participants as u8, min_signers as u8)? {
         return Err(AppSW::Deny);
     }
 
-    let data = DkgKeys.backup_keys()?;
-    let key = compute_key();
+    let data = dkg_keys.backup_keys()?;
+    let key = compute_backup_key();
 
-    let resp = encrypt(&key, data.as_slice())?;
+    let resp = seal_backup(&key, &header, data.as_slice())?;
 
     let total_chunks = save_result(ctx, resp.as_slice())?;
     comm.append(&total_chunks);
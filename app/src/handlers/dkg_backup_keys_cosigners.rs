@@ -0,0 +1,112 @@
+/*****************************************************************************
+ *   Ledger App Ironfish Rust.
+ *   (c) 2023 Ledger SAS.
+ *
+ *  Licensed under the Apache License, Version 2.0 (the "License");
+ *  you may not use this file except in compliance with the License.
+ *  You may obtain a copy of the License at
+ *
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *  Unless required by applicable law or agreed to in writing, software
+ *  distributed under the License is distributed on an "AS IS" BASIS,
+ *  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *  See the License for the specific language governing permissions and
+ *  limitations under the License.
+ *****************************************************************************/
+
+use crate::app_ui::run_action::ui_review_backup_keys_cosigners;
+use crate::bolos::zlog_stack;
+use crate::context::TxContext;
+use crate::crypto::backup::{seal_backup_for_identities, BackupHeader, BACKUP_VERSION_COSIGNERS};
+use crate::crypto::{derive_multisig_account, multisig_to_key_type};
+use crate::nvm::dkg_keys::DkgKeys;
+use crate::utils::fingerprint;
+use crate::utils::response::save_result;
+use crate::AppSW;
+use alloc::string::String;
+use alloc::vec::Vec;
+use ironfish_frost::participant::Identity;
+use ledger_device_sdk::io::Comm;
+
+/// Backs up the keys encrypted to the identities of some co-signers, instead of a key derived
+/// from our seed. If our seed is lost, any of them can hand the keys over to a replacement device.
+#[inline(never)]
+pub fn handler_dkg_backup_keys_cosigners(
+    comm: &mut Comm,
+    slot: u8,
+    ctx: &mut TxContext,
+) -> Result<(), AppSW> {
+    zlog_stack("start handler_dkg_backup_keys_cosigners\0");
+
+    let dkg_keys = DkgKeys::new(slot)?;
+
+    let identities = dkg_keys.load_identities()?;
+    let recipients_indexes = parse_recipients(
+        comm.get_data().map_err(|_| AppSW::WrongApduLength)?,
+        identities.len(),
+    )?;
+
+    let account_keys = derive_multisig_account(dkg_keys.data())?;
+    let public_address = multisig_to_key_type(&account_keys, 0u8)?;
+    drop(account_keys);
+
+    let header = BackupHeader {
+        version: BACKUP_VERSION_COSIGNERS,
+        public_address: public_address
+            .as_slice()
+            .try_into()
+            .map_err(|_| AppSW::KeyDeriveFail)?,
+        min_signers: dkg_keys.load_min_signers()? as u8,
+        participants: identities.len() as u8,
+    };
+
+    let recipients: Vec<Identity> = recipients_indexes
+        .iter()
+        .map(|index| identities[*index as usize].clone())
+        .collect();
+    drop(identities);
+
+    let recipients_fingerprints: Vec<(u8, String)> = recipients_indexes
+        .iter()
+        .zip(recipients.iter())
+        .map(|(index, identity)| (*index, fingerprint(&[identity.serialize().as_ref()])))
+        .collect();
+
+    if !ui_review_backup_keys_cosigners(
+        &header.public_address,
+        header.participants,
+        header.min_signers,
+        &recipients_fingerprints,
+    )? {
+        return Err(AppSW::Deny);
+    }
+
+    let data = dkg_keys.backup_keys()?;
+    let resp = seal_backup_for_identities(&header, data.as_slice(), &recipients)?;
+
+    let total_chunks = save_result(ctx, resp.as_slice())?;
+    comm.append(&total_chunks);
+
+    Ok(())
+}
+
+/// Recipients are given by their position in the stored identities: qty (u8), then the positions
+#[inline(never)]
+fn parse_recipients(data: &[u8], participants: usize) -> Result<Vec<u8>, AppSW> {
+    let (qty, indexes) = data.split_first().ok_or(AppSW::InvalidPayload)?;
+    if *qty == 0 || indexes.len() != *qty as usize {
+        return Err(AppSW::InvalidPayload);
+    }
+
+    for (i, index) in indexes.iter().enumerate() {
+        if *index as usize >= participants {
+            return Err(AppSW::InvalidIdentityIndex);
+        }
+        if indexes[..i].contains(index) {
+            return Err(AppSW::DuplicatedIdentity);
+        }
+    }
+
+    Ok(indexes.to_vec())
+}
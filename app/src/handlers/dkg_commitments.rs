@@ -32,10 +32,13 @@ use ledger_device_sdk::io::Comm;
 pub fn handler_dkg_commitments(
     comm: &mut Comm,
     chunk: u8,
+    slot: u8,
     ctx: &mut TxContext,
 ) -> Result<(), AppSW> {
     zlog_stack("start handler_dkg_commitments\0");
 
+    let dkg_keys = DkgKeys::new(slot)?;
+
     accumulate_data(comm, chunk, ctx)?;
     if !ctx.done {
         return Ok(());
@@ -46,15 +49,16 @@ pub fn handler_dkg_commitments(
     // By this point, the transaction should have already been reviewed.
     // Before proceeding, we need to ensure that the transaction was approved.
     // The transaction hash must be available and it should match the hash we received.
-    let current_hash = get_tx_hash().ok_or(AppSW::InvalidTxHash)?;
+    // It must also have been reviewed for the account we are about to sign with.
+    let current_hash = get_tx_hash(slot).ok_or(AppSW::InvalidTxHash)?;
 
     if current_hash != tx_hash {
         zlog_stack("tx hash mismatch\0");
         return Err(AppSW::InvalidTxHash);
     }
 
-    let key_package = DkgKeys.load_key_package()?;
-    let identities = DkgKeys.load_identities()?;
+    let key_package = dkg_keys.load_key_package()?;
+    let identities = dkg_keys.load_identities()?;
 
     let nonces = deterministic_signing_nonces(key_package.signing_share(), tx_hash, &identities);
 
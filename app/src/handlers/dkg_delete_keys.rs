@@ -0,0 +1,76 @@
+/*****************************************************************************
+ *   Ledger App Ironfish Rust.
+ *   (c) 2023 Ledger SAS.
+ *
+ *  Licensed under the Apache License, Version 2.0 (the "License");
+ *  you may not use this file except in compliance with the License.
+ *  You may obtain a copy of the License at
+ *
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *  Unless required by applicable law or agreed to in writing, software
+ *  distributed under the License is distributed on an "AS IS" BASIS,
+ *  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *  See the License for the specific language governing permissions and
+ *  limitations under the License.
+ *****************************************************************************/
+
+use crate::app_ui::run_action::ui_review_delete_account;
+use crate::bolos::zlog_stack;
+use crate::crypto::{derive_multisig_account, multisig_to_key_type};
+use crate::nvm::dkg_keys::{DkgKeyStatus, DkgKeys};
+use crate::AppSW;
+use alloc::vec::Vec;
+
+#[inline(never)]
+pub fn handler_dkg_delete_keys(slot: u8) -> Result<(), AppSW> {
+    zlog_stack("start handler_dkg_delete_keys\0");
+
+    let dkg_keys = DkgKeys::new(slot)?;
+    delete_account(slot, &dkg_keys)
+}
+
+/// Wipes the account slot once the user confirms it. Also used by the delete account menu entry.
+#[inline(never)]
+pub(crate) fn delete_account(slot: u8, dkg_keys: &DkgKeys) -> Result<(), AppSW> {
+    zlog_stack("start delete_account\0");
+
+    // A slot that can not be read can still be wiped
+    let summary = dkg_keys.summary().ok();
+    let public_address: Option<Vec<u8>> = match summary {
+        Some(ref summary) if matches!(summary.status, DkgKeyStatus::Completed) => {
+            derive_multisig_account(dkg_keys.data())
+                .and_then(|account_keys| multisig_to_key_type(&account_keys, 0))
+                .ok()
+        }
+        _ => None,
+    };
+
+    let (participants, min_signers) = summary
+        .as_ref()
+        .map(|summary| (summary.participants, summary.min_signers))
+        .unwrap_or((0, 0));
+
+    if !ui_review_delete_account(
+        slot,
+        slot_status_label(dkg_keys),
+        public_address.as_deref(),
+        participants,
+        min_signers,
+    )? {
+        return Err(AppSW::Deny);
+    }
+
+    dkg_keys.delete_keys()
+}
+
+/// Short description of what the slot holds, as shown on screen
+#[inline(never)]
+pub(crate) fn slot_status_label(dkg_keys: &DkgKeys) -> &'static str {
+    match dkg_keys.get_keys_status() {
+        Ok(DkgKeyStatus::Idle) => "Empty",
+        Ok(DkgKeyStatus::Initiated) => "DKG in progress",
+        Ok(DkgKeyStatus::Completed) => "Account ready",
+        Err(_) => "Unreadable",
+    }
+}
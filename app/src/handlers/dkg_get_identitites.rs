@@ -24,10 +24,14 @@ use alloc::vec::Vec;
 use ledger_device_sdk::io::Comm;
 
 #[inline(never)]
-pub fn handler_dkg_get_identities(comm: &mut Comm, ctx: &mut TxContext) -> Result<(), AppSW> {
+pub fn handler_dkg_get_identities(
+    comm: &mut Comm,
+    slot: u8,
+    ctx: &mut TxContext,
+) -> Result<(), AppSW> {
     zlog_stack("handler_dkg_get_identities\0");
 
-    let identities = DkgKeys.load_identities()?;
+    let identities = DkgKeys::new(slot)?.load_identities()?;
     let resp: Vec<_> = identities.iter().flat_map(|s| s.serialize()).collect();
 
     let total_chunks = save_result(ctx, resp.as_slice())?;
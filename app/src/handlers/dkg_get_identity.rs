@@ -18,11 +18,10 @@
 use crate::app_ui::run_action::ui_review_get_identity;
 use crate::bolos::zlog_stack;
 use crate::crypto::compute_dkg_secret;
+use crate::ironfish::constants::MAX_IDENTITY_INDEX;
 use crate::AppSW;
 use ledger_device_sdk::io::Comm;
 
-const MAX_IDENTITY_INDEX: u8 = 5;
-
 #[inline(never)]
 pub fn handler_dkg_get_identity(comm: &mut Comm, require_review: bool) -> Result<(), AppSW> {
     zlog_stack("start handler_identity\0");
@@ -26,13 +26,20 @@ use core::ptr;
 use ledger_device_sdk::io::Comm;
 
 #[inline(never)]
-pub fn handler_dkg_get_keys(comm: &mut Comm, review: bool, key_type: u8) -> Result<(), AppSW> {
+pub fn handler_dkg_get_keys(
+    comm: &mut Comm,
+    review: bool,
+    key_type: u8,
+    slot: u8,
+) -> Result<(), AppSW> {
     zlog_stack("start handler_dkg_get_keys\0");
 
+    let dkg_keys = DkgKeys::new(slot)?;
+
     let mut resp: Vec<u8>;
 
     if key_type == 3 {
-        let identity_index = DkgKeys.load_identity_index()?;
+        let identity_index = dkg_keys.load_identity_index()?;
         let identity = compute_dkg_secret(identity_index as u8).to_identity();
         resp = identity.serialize().as_slice().to_vec();
 
@@ -40,7 +47,7 @@ pub fn handler_dkg_get_keys(comm: &mut Comm, review: bool, key_type: u8) -> Resu
             return Err(AppSW::Deny);
         }
     } else {
-        let account_keys = derive_multisig_account(None)?;
+        let account_keys = derive_multisig_account(dkg_keys.data())?;
         resp = multisig_to_key_type(&account_keys, key_type)?;
         drop(account_keys);
 
@@ -24,12 +24,17 @@ use ironfish_frost::dkg::round3::PublicKeyPackage;
 use ledger_device_sdk::io::Comm;
 
 #[inline(never)]
-pub fn handler_dkg_get_public_package(comm: &mut Comm, ctx: &mut TxContext) -> Result<(), AppSW> {
+pub fn handler_dkg_get_public_package(
+    comm: &mut Comm,
+    slot: u8,
+    ctx: &mut TxContext,
+) -> Result<(), AppSW> {
     zlog_stack("start handler_dkg_get_pub_pack\0");
 
-    let identities = DkgKeys.load_identities()?;
-    let min_signers = DkgKeys.load_min_signers()?;
-    let frost_public_key_package = DkgKeys.load_frost_public_key_package()?;
+    let dkg_keys = DkgKeys::new(slot)?;
+    let identities = dkg_keys.load_identities()?;
+    let min_signers = dkg_keys.load_min_signers()?;
+    let frost_public_key_package = dkg_keys.load_frost_public_key_package()?;
 
     let p = PublicKeyPackage::from_frost(frost_public_key_package, identities, min_signers as u16);
 
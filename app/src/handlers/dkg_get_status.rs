@@ -0,0 +1,39 @@
+/*****************************************************************************
+ *   Ledger App Ironfish Rust.
+ *   (c) 2023 Ledger SAS.
+ *
+ *  Licensed under the Apache License, Version 2.0 (the "License");
+ *  you may not use this file except in compliance with the License.
+ *  You may obtain a copy of the License at
+ *
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *  Unless required by applicable law or agreed to in writing, software
+ *  distributed under the License is distributed on an "AS IS" BASIS,
+ *  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *  See the License for the specific language governing permissions and
+ *  limitations under the License.
+ *****************************************************************************/
+
+use crate::bolos::zlog_stack;
+use crate::nvm::dkg_keys::DkgKeys;
+use crate::AppSW;
+use ledger_device_sdk::io::Comm;
+
+/// Reports where the DKG of the account slot stands, so an interrupted ceremony
+/// can be detected and aborted.
+#[inline(never)]
+pub fn handler_dkg_get_status(comm: &mut Comm, slot: u8) -> Result<(), AppSW> {
+    zlog_stack("start handler_dkg_get_status\0");
+
+    let dkg_keys = DkgKeys::new(slot)?;
+    let summary = dkg_keys.summary()?;
+
+    comm.append(&[
+        summary.status as u8,
+        summary.identity_index,
+        summary.min_signers,
+        summary.participants,
+    ]);
+    Ok(())
+}
@@ -0,0 +1,71 @@
+/*****************************************************************************
+ *   Ledger App Ironfish Rust.
+ *   (c) 2023 Ledger SAS.
+ *
+ *  Licensed under the Apache License, Version 2.0 (the "License");
+ *  you may not use this file except in compliance with the License.
+ *  You may obtain a copy of the License at
+ *
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *  Unless required by applicable law or agreed to in writing, software
+ *  distributed under the License is distributed on an "AS IS" BASIS,
+ *  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *  See the License for the specific language governing permissions and
+ *  limitations under the License.
+ *****************************************************************************/
+
+use crate::bolos::zlog_stack;
+use crate::crypto::{derive_multisig_account, multisig_to_key_type};
+use crate::nvm::dkg_keys::{DkgKeyStatus, DkgKeys, DKG_KEYS_SLOTS};
+use crate::parser::PUBLIC_ADDRESS_SIZE;
+use crate::AppSW;
+use alloc::vec::Vec;
+use ledger_device_sdk::io::Comm;
+
+// Reported for slots whose content can not be read
+const INVALID_SLOT_STATUS: u8 = 0xFF;
+
+#[inline(never)]
+pub fn handler_dkg_list_accounts(comm: &mut Comm) -> Result<(), AppSW> {
+    zlog_stack("start handler_dkg_list_accounts\0");
+
+    let mut resp: Vec<u8> = Vec::with_capacity(1 + DKG_KEYS_SLOTS * (3 + PUBLIC_ADDRESS_SIZE));
+    resp.push(DKG_KEYS_SLOTS as u8);
+
+    for slot in 0..DKG_KEYS_SLOTS {
+        let dkg_keys = DkgKeys::new(slot as u8)?;
+        resp.extend_from_slice(&slot_entry(&dkg_keys));
+    }
+
+    comm.append(resp.as_slice());
+    Ok(())
+}
+
+/// Status, min signers, participants and public address of the account in the slot.
+/// The address is all zeros unless the DKG is completed.
+#[inline(never)]
+fn slot_entry(dkg_keys: &DkgKeys) -> [u8; 3 + PUBLIC_ADDRESS_SIZE] {
+    let mut entry = [0u8; 3 + PUBLIC_ADDRESS_SIZE];
+
+    let Ok(summary) = dkg_keys.summary() else {
+        entry[0] = INVALID_SLOT_STATUS;
+        return entry;
+    };
+
+    entry[0] = summary.status as u8;
+    entry[1] = summary.min_signers;
+    entry[2] = summary.participants;
+
+    if let DkgKeyStatus::Completed = summary.status {
+        let address = derive_multisig_account(dkg_keys.data())
+            .and_then(|account_keys| multisig_to_key_type(&account_keys, 0));
+
+        match address {
+            Ok(address) => entry[3..].copy_from_slice(&address),
+            Err(_) => entry[0] = INVALID_SLOT_STATUS,
+        }
+    }
+
+    entry
+}
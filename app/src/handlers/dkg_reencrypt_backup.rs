@@ -0,0 +1,125 @@
+/*****************************************************************************
+ *   Ledger App Ironfish Rust.
+ *   (c) 2023 Ledger SAS.
+ *
+ *  Licensed under the Apache License, Version 2.0 (the "License");
+ *  you may not use this file except in compliance with the License.
+ *  You may obtain a copy of the License at
+ *
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *  Unless required by applicable law or agreed to in writing, software
+ *  distributed under the License is distributed on an "AS IS" BASIS,
+ *  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *  See the License for the specific language governing permissions and
+ *  limitations under the License.
+ *****************************************************************************/
+
+use crate::accumulator::accumulate_data;
+use crate::app_ui::run_action::ui_review_reencrypt_backup;
+use crate::bolos::zlog_stack;
+use crate::context::TxContext;
+use crate::crypto::backup::{open_backup_with_secret, seal_backup_for_identities};
+use crate::crypto::{compute_dkg_secret, derive_multisig_account, multisig_to_key_type};
+use crate::ironfish::constants::IDENTITY_LEN;
+use crate::nvm::buffer::Buffer;
+use crate::nvm::dkg_keys::DkgKeys;
+use crate::nvm::DkgKeysReader;
+use crate::utils::fingerprint;
+use crate::utils::response::save_result;
+use crate::AppSW;
+use alloc::string::String;
+use ironfish_frost::participant::Identity;
+use ledger_device_sdk::io::Comm;
+
+pub struct Tx<'a> {
+    backup: &'a [u8],
+    new_identity: Identity,
+}
+
+/// Hands a backup encrypted to co-signers over to a replacement device. We must be one of the
+/// recipients and hold the same account. The keys are encrypted again, to the new identity only.
+#[inline(never)]
+pub fn handler_dkg_reencrypt_backup(
+    comm: &mut Comm,
+    chunk: u8,
+    slot: u8,
+    ctx: &mut TxContext,
+) -> Result<(), AppSW> {
+    zlog_stack("start handler_dkg_reencrypt_backup\0");
+
+    let dkg_keys = DkgKeys::new(slot)?;
+
+    accumulate_data(comm, chunk, ctx)?;
+    if !ctx.done {
+        return Ok(());
+    }
+
+    let tx = parse_tx(&ctx.buffer)?;
+
+    let account_keys = derive_multisig_account(dkg_keys.data())?;
+    let public_address = multisig_to_key_type(&account_keys, 0u8)?;
+    drop(account_keys);
+
+    let secret = compute_dkg_secret(dkg_keys.load_identity_index()? as u8);
+    let (header, data) = open_backup_with_secret(tx.backup, &secret)?;
+    drop(secret);
+
+    if header.public_address[..] != public_address[..] {
+        return Err(AppSW::BackupAccountMismatch);
+    }
+
+    let owner = backup_owner(&data)?;
+    let new_identity_fingerprint = fingerprint(&[tx.new_identity.serialize().as_ref()]);
+
+    if !ui_review_reencrypt_backup(&header.public_address, &owner, &new_identity_fingerprint)? {
+        return Err(AppSW::Deny);
+    }
+
+    let resp = seal_backup_for_identities(&header, &data, &[tx.new_identity])?;
+    drop(data);
+
+    let total_chunks = save_result(ctx, resp.as_slice())?;
+    comm.append(&total_chunks);
+
+    Ok(())
+}
+
+#[inline(never)]
+fn parse_tx(buffer: &Buffer) -> Result<Tx, AppSW> {
+    zlog_stack("start parse_tx reencrypt_backup\0");
+
+    let mut tx_pos: usize = 0;
+
+    let len = buffer.get_u16(tx_pos)?;
+    tx_pos += 2;
+
+    let backup = buffer.get_slice(tx_pos, tx_pos + len)?;
+    tx_pos += len;
+
+    let data = buffer.get_slice(tx_pos, tx_pos + IDENTITY_LEN)?;
+    let new_identity = Identity::deserialize_from(data).map_err(|_| AppSW::InvalidIdentity)?;
+    tx_pos += IDENTITY_LEN;
+
+    if tx_pos != buffer.pos {
+        return Err(AppSW::InvalidPayload);
+    }
+
+    Ok(Tx {
+        backup,
+        new_identity,
+    })
+}
+
+/// Position and identity fingerprint of the participant the backed up keys belong to
+#[inline(never)]
+fn backup_owner(data: &[u8]) -> Result<(u8, String), AppSW> {
+    let identifier = *DkgKeysReader::load_key_package(data)?.identifier();
+
+    DkgKeysReader::load_identities(data)?
+        .iter()
+        .enumerate()
+        .find(|(_, identity)| identity.to_frost_identifier() == identifier)
+        .map(|(index, identity)| (index as u8, fingerprint(&[identity.serialize().as_ref()])))
+        .ok_or(AppSW::InvalidKeyPackage)
+}
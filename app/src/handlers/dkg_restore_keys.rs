@@ -15,57 +15,151 @@
  *  limitations under the License.
  *****************************************************************************/
 use crate::accumulator::accumulate_data;
-use crate::app_ui::run_action::ui_review_restore_keys;
+use crate::app_ui::run_action::{ui_review_restore_keys, ui_review_restore_replace_account};
 use crate::bolos::zlog_stack;
 use crate::context::TxContext;
-use crate::crypto::chacha20poly::{compute_key, decrypt, NONCE_LEN};
-use crate::crypto::{derive_multisig_account, multisig_to_key_type};
-use crate::nvm::dkg_keys::DkgKeys;
+use crate::crypto::backup::{
+    compute_backup_key, compute_legacy_backup_key, open_backup, open_backup_with_secret,
+    BackupHeader, BACKUP_VERSION_COSIGNERS,
+};
+use crate::crypto::{
+    compute_dkg_secret, derive_multisig_account, multisig_to_key_type, KeysDataGuard,
+};
+use crate::ironfish::constants::MAX_IDENTITY_INDEX;
+use crate::nvm::dkg_keys::{DkgKeyStatus, DkgKeys};
 use crate::nvm::DkgKeysReader;
 use crate::AppSW;
+use alloc::vec::Vec;
 use ledger_device_sdk::io::Comm;
 
+/// Keys data of an opened and checked backup, along with the account it holds
+pub(crate) struct RestoredAccount {
+    pub data: KeysDataGuard,
+    pub public_address: Vec<u8>,
+    pub min_signers: u8,
+    pub participants: u8,
+}
+
 #[inline(never)]
 pub fn handler_dkg_restore_keys(
     comm: &mut Comm,
     chunk: u8,
+    slot: u8,
     ctx: &mut TxContext,
 ) -> Result<(), AppSW> {
     zlog_stack("start handler_dkg_restore_keys\0");
 
+    let dkg_keys = DkgKeys::new(slot)?;
+
     accumulate_data(comm, chunk, ctx)?;
     if !ctx.done {
         return Ok(());
     }
 
-    if ctx.buffer.pos < NONCE_LEN {
-        return Err(AppSW::InvalidPayload);
+    let payload = ctx.buffer.get_slice(0, ctx.buffer.pos)?;
+    let account = open_any_backup(payload)?;
+
+    if !confirm_account_replacement(&dkg_keys, &account.public_address)? {
+        return Err(AppSW::Deny);
     }
 
-    let split_pos = ctx.buffer.pos - NONCE_LEN;
-    let encrypted_data = ctx.buffer.get_slice(0, split_pos)?;
-    let nonce = ctx.buffer.get_slice(split_pos, ctx.buffer.pos)?;
+    if !ui_review_restore_keys(
+        account.public_address.clone(),
+        account.participants,
+        account.min_signers,
+    )? {
+        return Err(AppSW::Deny);
+    }
 
-    let decryption_key = compute_key();
-    let keys_data_guard = decrypt(&decryption_key, encrypted_data, nonce)?;
+    dkg_keys.restore_keys(&account.data)
+}
+
+/// Decrypts a backup in any of the supported formats, and checks the keys it holds are
+/// complete and consistent with its header. Also used to verify backups.
+#[inline(never)]
+pub(crate) fn open_any_backup(payload: &[u8]) -> Result<RestoredAccount, AppSW> {
+    zlog_stack("start open_any_backup\0");
+
+    let (header, data) = match BackupHeader::parse(payload) {
+        Some(header) if header.version == BACKUP_VERSION_COSIGNERS => {
+            let (header, data) = open_cosigners_backup(payload)?;
+            (Some(header), data)
+        }
+        _ => open_backup(payload, &compute_backup_key(), &compute_legacy_backup_key())?,
+    };
+
+    DkgKeysReader::validate_keys(&data)?;
+
+    let account_keys = derive_multisig_account(&data)?;
+    let public_address = multisig_to_key_type(&account_keys, 0u8)?;
+    drop(account_keys);
 
-    review_restore_keys(&keys_data_guard)?;
+    let min_signers = DkgKeysReader::load_min_signers(&data)? as u8;
+    let participants = DkgKeysReader::load_identities(&data)?.len() as u8;
 
-    DkgKeys.restore_keys(&keys_data_guard)
+    // The cleartext header must describe the account the backup actually holds
+    if let Some(header) = header {
+        if header.public_address[..] != public_address[..]
+            || header.min_signers != min_signers
+            || header.participants != participants
+        {
+            return Err(AppSW::InvalidBackup);
+        }
+    }
+
+    Ok(RestoredAccount {
+        data,
+        public_address,
+        min_signers,
+        participants,
+    })
 }
 
+/// Public address of the account stored in the slot, if the DKG was completed on it.
+/// Used to tell whether an operation would replace it.
 #[inline(never)]
-fn review_restore_keys(data: &[u8]) -> Result<(), AppSW> {
-    let account_keys = derive_multisig_account(Some(data))?;
+pub(crate) fn stored_account_address(dkg_keys: &DkgKeys) -> Result<Option<Vec<u8>>, AppSW> {
+    zlog_stack("start stored_account_address\0");
+
+    if !matches!(dkg_keys.get_keys_status(), Ok(DkgKeyStatus::Completed)) {
+        return Ok(None);
+    }
+
+    let account_keys = derive_multisig_account(dkg_keys.data())?;
     let public_address = multisig_to_key_type(&account_keys, 0u8)?;
     drop(account_keys);
 
-    let min_signers = DkgKeysReader::load_min_signers(data)?;
-    let participants = DkgKeysReader::load_identities(data)?.len();
+    Ok(Some(public_address))
+}
 
-    if !ui_review_restore_keys(public_address, participants as u8, min_signers as u8)? {
-        return Err(AppSW::Deny);
+/// Backups encrypted to co-signers are restored on the device of one of the recipients,
+/// which may have joined with any identity index
+#[inline(never)]
+fn open_cosigners_backup(payload: &[u8]) -> Result<(BackupHeader, KeysDataGuard), AppSW> {
+    zlog_stack("start open_cosigners_backup\0");
+
+    for identity_index in 0..=MAX_IDENTITY_INDEX {
+        let secret = compute_dkg_secret(identity_index);
+        match open_backup_with_secret(payload, &secret) {
+            Ok(opened) => return Ok(opened),
+            Err(AppSW::DecryptionFail) => continue,
+            Err(e) => return Err(e),
+        }
     }
 
-    Ok(())
+    Err(AppSW::DecryptionFail)
+}
+
+/// Restoring the same account again is fine, but a different account stored in the slot
+/// is only replaced if the user explicitly accepts losing it.
+#[inline(never)]
+fn confirm_account_replacement(dkg_keys: &DkgKeys, public_address: &[u8]) -> Result<bool, AppSW> {
+    zlog_stack("start confirm_account_replacement\0");
+
+    match stored_account_address(dkg_keys)? {
+        Some(current_address) if current_address[..] != public_address[..] => {
+            ui_review_restore_replace_account(&current_address, public_address)
+        }
+        _ => Ok(true),
+    }
 }
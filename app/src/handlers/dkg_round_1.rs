@@ -16,10 +16,11 @@
  *****************************************************************************/
 
 use crate::accumulator::accumulate_data;
-use crate::app_ui::run_action::ui_review_dkg_round1;
+use crate::app_ui::run_action::{ui_review_dkg_round1, ui_review_replace_account};
 use crate::bolos::{zlog, zlog_stack};
 use crate::context::TxContext;
-use crate::crypto::compute_dkg_secret;
+use crate::crypto::{compute_dkg_secret, dkg_session_digest, dkg_session_fingerprint};
+use crate::handlers::stored_account_address;
 use crate::ironfish::constants::{IDENTITY_LEN, MAX_PARTICIPANTS};
 use crate::nvm::buffer::Buffer;
 use crate::nvm::dkg_keys::DkgKeys;
@@ -38,9 +39,16 @@ pub struct Tx {
 }
 
 #[inline(never)]
-pub fn handler_dkg_round_1(comm: &mut Comm, chunk: u8, ctx: &mut TxContext) -> Result<(), AppSW> {
+pub fn handler_dkg_round_1(
+    comm: &mut Comm,
+    chunk: u8,
+    slot: u8,
+    ctx: &mut TxContext,
+) -> Result<(), AppSW> {
     zlog_stack("start handler_dkg_round_1\0");
 
+    let dkg_keys = DkgKeys::new(slot)?;
+
     accumulate_data(comm, chunk, ctx)?;
     if !ctx.done {
         return Ok(());
@@ -48,15 +56,43 @@ pub fn handler_dkg_round_1(comm: &mut Comm, chunk: u8, ctx: &mut TxContext) -> R
 
     let mut tx: Tx = parse_tx(&ctx.buffer)?;
 
-    if !ui_review_dkg_round1(tx.identity_index, tx.min_signers, tx.identities.len() as u8)? {
+    let dkg_secret = compute_dkg_secret(tx.identity_index);
+    check_participants(&dkg_secret, &tx)?;
+
+    let identities: Vec<_> = tx.identities.iter().map(|i| i.serialize()).collect();
+    let session_fingerprint = dkg_session_fingerprint(
+        tx.min_signers,
+        identities.iter().map(|i| i.as_slice()).collect(),
+    );
+
+    // Starting a new DKG wipes the account stored on the slot
+    if !confirm_account_replacement(&dkg_keys)? {
         return Err(AppSW::Deny);
     }
 
-    let dkg_secret = compute_dkg_secret(tx.identity_index);
+    if !ui_review_dkg_round1(
+        tx.identity_index,
+        tx.min_signers,
+        tx.identities.len() as u8,
+        &session_fingerprint,
+    )? {
+        return Err(AppSW::Deny);
+    }
 
     let resp = compute_dkg_round_1(comm, &dkg_secret, &mut tx)?;
 
-    DkgKeys.save_round_1_data(&tx.identities, tx.min_signers, tx.identity_index)?;
+    let session_digest = dkg_session_digest(
+        tx.identity_index,
+        tx.min_signers,
+        identities.iter().map(|i| i.as_slice()).collect(),
+    );
+
+    dkg_keys.save_round_1_data(
+        &tx.identities,
+        tx.min_signers,
+        tx.identity_index,
+        &session_digest,
+    )?;
 
     let total_chunks = save_result(ctx, resp.as_slice())?;
     comm.append(&total_chunks);
@@ -99,6 +135,51 @@ fn parse_tx(buffer: &Buffer) -> Result<Tx, AppSW> {
     })
 }
 
+/// Checks the participants list is consistent before joining the DKG: our own identity
+/// must be listed, no identity can be repeated, and min signers must be reachable
+#[inline(never)]
+fn check_participants(secret: &Secret, tx: &Tx) -> Result<(), AppSW> {
+    zlog_stack("start check_participants\0");
+
+    let identities: Vec<_> = tx
+        .identities
+        .iter()
+        .map(|identity| identity.serialize())
+        .collect();
+
+    for (i, identity) in identities.iter().enumerate() {
+        if identities[..i].contains(identity) {
+            return Err(AppSW::DuplicatedIdentity);
+        }
+    }
+
+    let own_identity = secret.to_identity().serialize();
+    if !identities.contains(&own_identity) {
+        return Err(AppSW::OwnIdentityNotFound);
+    }
+
+    if tx.min_signers < 2 || tx.min_signers as usize > identities.len() {
+        return Err(AppSW::InvalidMinSigners);
+    }
+
+    Ok(())
+}
+
+/// A completed account is only replaced if the user explicitly accepts losing it.
+/// Slots without a completed account can be used right away.
+#[inline(never)]
+fn confirm_account_replacement(dkg_keys: &DkgKeys) -> Result<bool, AppSW> {
+    zlog_stack("start confirm_account_replacement\0");
+
+    let summary = dkg_keys.summary()?;
+    match stored_account_address(dkg_keys)? {
+        Some(public_address) => {
+            ui_review_replace_account(&public_address, summary.participants, summary.min_signers)
+        }
+        None => Ok(true),
+    }
+}
+
 fn compute_dkg_round_1(_comm: &mut Comm, secret: &Secret, tx: &mut Tx) -> Result<Vec<u8>, AppSW> {
     zlog("start compute_dkg_round_1\n\0");
 
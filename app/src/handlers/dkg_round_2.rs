@@ -19,8 +19,9 @@ use crate::accumulator::accumulate_data;
 use crate::app_ui::run_action::ui_review_dkg_round2;
 use crate::bolos::zlog_stack;
 use crate::context::TxContext;
-use crate::crypto::compute_dkg_secret;
+use crate::crypto::{check_dkg_session, compute_dkg_secret};
 use crate::nvm::buffer::Buffer;
+use crate::nvm::dkg_keys::DkgKeys;
 use crate::utils::response::save_result;
 use crate::AppSW;
 use alloc::vec::Vec;
@@ -31,9 +32,16 @@ use ledger_device_sdk::io::Comm;
 use ledger_device_sdk::random::LedgerRng;
 
 #[inline(never)]
-pub fn handler_dkg_round_2(comm: &mut Comm, chunk: u8, ctx: &mut TxContext) -> Result<(), AppSW> {
+pub fn handler_dkg_round_2(
+    comm: &mut Comm,
+    chunk: u8,
+    slot: u8,
+    ctx: &mut TxContext,
+) -> Result<(), AppSW> {
     zlog_stack("start handler_dkg_round_2\0");
 
+    let dkg_keys = DkgKeys::new(slot)?;
+
     accumulate_data(comm, chunk, ctx)?;
     if !ctx.done {
         return Ok(());
@@ -44,13 +52,16 @@ pub fn handler_dkg_round_2(comm: &mut Comm, chunk: u8, ctx: &mut TxContext) -> R
     let (round_1_secret_package, _current_pos) =
         parse_round_1_secret_package(&ctx.buffer, current_pos)?;
 
+    // Round 1 packages must come from the participants of the DKG we started on round 1
+    check_session(&dkg_keys, identity_index, &round_1_public_packages)?;
+
     if !ui_review_dkg_round2(identity_index, round_1_public_packages.len() as u8)? {
         return Err(AppSW::Deny);
     }
 
     let (mut round2_secret_package_vec, round2_public_package) = compute_dkg_round_2(
         identity_index,
-        round_1_public_packages,
+        &round_1_public_packages,
         round_1_secret_package,
     )?;
 
@@ -63,11 +74,13 @@ pub fn handler_dkg_round_2(comm: &mut Comm, chunk: u8, ctx: &mut TxContext) -> R
     Ok(())
 }
 
+/// Packages are kept serialized, and only deserialized when they are needed,
+/// so they are not all held in memory while the user reviews the operation
 #[inline(never)]
 fn parse_round_1_public_packages(
     buffer: &Buffer,
     mut tx_pos: usize,
-) -> Result<(Vec<PublicPackage>, usize), AppSW> {
+) -> Result<(Vec<&[u8]>, usize), AppSW> {
     zlog_stack("start parse round1 - 1\0");
 
     let elements = buffer.get_element(tx_pos)?;
@@ -76,19 +89,43 @@ fn parse_round_1_public_packages(
     let len = buffer.get_u16(tx_pos)?;
     tx_pos += 2;
 
-    let mut round_1_public_packages: Vec<PublicPackage> = Vec::with_capacity(elements as usize);
+    let mut round_1_public_packages: Vec<&[u8]> = Vec::with_capacity(elements as usize);
     for _i in 0..elements {
         let data = buffer.get_slice(tx_pos, tx_pos + len)?;
-        let public_package =
-            PublicPackage::deserialize_from(data).map_err(|_| AppSW::InvalidPublicPackage)?;
         tx_pos += len;
 
-        round_1_public_packages.push(public_package);
+        round_1_public_packages.push(data);
     }
 
     Ok((round_1_public_packages, tx_pos))
 }
 
+#[inline(never)]
+fn check_session(
+    dkg_keys: &DkgKeys,
+    identity_index: u8,
+    round_1_public_packages: &[&[u8]],
+) -> Result<(), AppSW> {
+    zlog_stack("start check_session round2\0");
+
+    let identities = round_1_public_packages
+        .iter()
+        .map(|p| {
+            PublicPackage::deserialize_from(*p)
+                .map(|p| p.identity().serialize())
+                .map_err(|_| AppSW::InvalidPublicPackage)
+        })
+        .collect::<Result<Vec<_>, AppSW>>()?;
+
+    check_dkg_session(
+        dkg_keys,
+        identity_index,
+        identities.iter().map(|i| i.as_slice()).collect(),
+    )?;
+
+    Ok(())
+}
+
 #[inline(never)]
 fn parse_round_1_secret_package(
     buffer: &Buffer,
@@ -107,11 +144,16 @@ fn parse_round_1_secret_package(
 #[inline(never)]
 fn compute_dkg_round_2(
     identity_index: u8,
-    round_1_public_packages: Vec<PublicPackage>,
+    round_1_public_packages: &[&[u8]],
     round_1_secret_package: &[u8],
 ) -> Result<(Vec<u8>, CombinedPublicPackage), AppSW> {
     zlog_stack("start compute_dkg_round_2\0");
 
+    let round_1_public_packages = round_1_public_packages
+        .iter()
+        .map(|p| PublicPackage::deserialize_from(*p).map_err(|_| AppSW::InvalidPublicPackage))
+        .collect::<Result<Vec<_>, AppSW>>()?;
+
     let rng = LedgerRng {};
     let secret = compute_dkg_secret(identity_index);
 
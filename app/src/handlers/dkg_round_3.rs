@@ -0,0 +1,298 @@
+/*****************************************************************************
+ *   Ledger App Ironfish Rust.
+ *   (c) 2023 Ledger SAS.
+ *
+ *  Licensed under the Apache License, Version 2.0 (the "License");
+ *  you may not use this file except in compliance with the License.
+ *  You may obtain a copy of the License at
+ *
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *  Unless required by applicable law or agreed to in writing, software
+ *  distributed under the License is distributed on an "AS IS" BASIS,
+ *  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *  See the License for the specific language governing permissions and
+ *  limitations under the License.
+ *****************************************************************************/
+
+use crate::accumulator::accumulate_data;
+use crate::app_ui::run_action::{ui_review_dkg_round3, DkgAccountSummary};
+use crate::bolos::zlog_stack;
+use crate::context::TxContext;
+use crate::crypto::{
+    check_dkg_session, compute_dkg_secret, dkg_session_fingerprint, group_key_fingerprint,
+    multisig_to_key_type, public_key_package_digest,
+};
+use crate::ironfish::multisig::derive_account_keys;
+use crate::nvm::buffer::Buffer;
+use crate::nvm::dkg_keys::DkgKeys;
+use crate::parser::PUBLIC_ADDRESS_SIZE;
+use crate::utils::response::save_result;
+use crate::AppSW;
+use alloc::vec::Vec;
+use core::ptr;
+use ironfish_frost::dkg;
+use ironfish_frost::dkg::group_key::GroupSecretKey;
+use ironfish_frost::dkg::round1::PublicPackage;
+use ironfish_frost::dkg::round2::CombinedPublicPackage;
+use ironfish_frost::frost::keys::KeyPackage;
+use ironfish_frost::frost::keys::PublicKeyPackage as FrostPublicKeyPackage;
+use ledger_device_sdk::io::Comm;
+
+pub struct Tx {
+    identity_index: u8,
+    round_1_public_packages: Vec<PublicPackage>,
+    round_2_public_packages: Vec<CombinedPublicPackage>,
+    round_2_secret_package: Vec<u8>,
+}
+
+/// Full version of round 3. Unlike round 3 min, it takes the complete round 1 and
+/// round 2 public package sets, including the ones generated by this device.
+#[inline(never)]
+pub fn handler_dkg_round_3(
+    comm: &mut Comm,
+    chunk: u8,
+    slot: u8,
+    ctx: &mut TxContext,
+) -> Result<(), AppSW> {
+    zlog_stack("start handler_dkg_round_3\0");
+
+    let dkg_keys = DkgKeys::new(slot)?;
+
+    accumulate_data(comm, chunk, ctx)?;
+    if !ctx.done {
+        return Ok(());
+    }
+
+    // Try to deserialize the transaction
+    let tx = parse_tx(&ctx.buffer)?;
+    let (min_signers, participants) = check_session(&dkg_keys, &tx)?;
+
+    let identities: Vec<_> = tx
+        .round_1_public_packages
+        .iter()
+        .map(|p| p.identity().serialize())
+        .collect();
+    let session_fingerprint = dkg_session_fingerprint(
+        min_signers,
+        identities.iter().map(|i| i.as_slice()).collect(),
+    );
+    drop(identities);
+
+    // Keys are computed before asking the user, so the resulting group key can be displayed.
+    // Nothing is saved unless the operation is approved.
+    let (key_package, public_key_package, group_secret_key) = compute_dkg_round_3(&tx)?;
+
+    // Every participant sends a group secret key shard along with its round 1 package
+    let approved = match round_3_result(&public_key_package, &group_secret_key) {
+        Ok((group_key, resp)) => ui_review_dkg_round3(
+            tx.identity_index,
+            tx.round_1_public_packages.len() as u8,
+            tx.round_2_public_packages.len() as u8,
+            participants,
+            None,
+            &DkgAccountSummary {
+                session_fingerprint: &session_fingerprint,
+                group_key_fingerprint: &group_key_fingerprint(&group_key),
+                public_address: &resp[..PUBLIC_ADDRESS_SIZE],
+            },
+        )
+        .map(|approved| approved.then_some(resp)),
+        Err(e) => Err(e),
+    };
+    drop(tx);
+
+    save_round_3(
+        comm,
+        ctx,
+        &dkg_keys,
+        approved,
+        key_package,
+        public_key_package,
+        group_secret_key,
+    )
+}
+
+/// Saves the keys resulting from round 3 if the operation was approved, and sends back the
+/// round 3 response. Secret material is zeroed out either way.
+#[inline(never)]
+pub(super) fn save_round_3(
+    comm: &mut Comm,
+    ctx: &mut TxContext,
+    dkg_keys: &DkgKeys,
+    approved: Result<Option<Vec<u8>>, AppSW>,
+    mut key_package: KeyPackage,
+    public_key_package: FrostPublicKeyPackage,
+    mut group_secret_key: GroupSecretKey,
+) -> Result<(), AppSW> {
+    zlog_stack("start save_round_3\0");
+
+    let result = match approved {
+        Ok(Some(resp)) => dkg_keys
+            .save_keys(&key_package, public_key_package, &group_secret_key)
+            .map(|_| resp),
+        Ok(None) => Err(AppSW::Deny),
+        Err(e) => Err(e),
+    };
+
+    // Zero out memory for the response data
+    unsafe {
+        ptr::write_bytes(&mut group_secret_key as *mut GroupSecretKey, 0, 1);
+        ptr::write_bytes(&mut key_package as *mut KeyPackage, 0, 1);
+    }
+
+    let resp = result?;
+    let total_chunks = save_result(ctx, resp.as_slice())?;
+    comm.append(&total_chunks);
+    Ok(())
+}
+
+/// Returns the group public key, along with the round 3 response: the public address of
+/// the new account followed by the digest of the public key package. It allows the host
+/// to check all the participants ended up with the same account in one step.
+#[inline(never)]
+pub(super) fn round_3_result(
+    public_key_package: &FrostPublicKeyPackage,
+    group_secret_key: &GroupSecretKey,
+) -> Result<([u8; 32], Vec<u8>), AppSW> {
+    zlog_stack("start round_3_result\0");
+
+    let group_key: [u8; 32] = public_key_package
+        .verifying_key()
+        .serialize()
+        .map_err(|_| AppSW::DkgRound3Fail)?
+        .as_slice()
+        .try_into()
+        .map_err(|_| AppSW::DkgRound3Fail)?;
+
+    let account_keys = derive_account_keys(&group_key, group_secret_key);
+    let mut resp = multisig_to_key_type(&account_keys, 0)?;
+    drop(account_keys);
+
+    let public_key_package = public_key_package
+        .serialize()
+        .map_err(|_| AppSW::DkgRound3Fail)?;
+    resp.extend_from_slice(&public_key_package_digest(public_key_package.as_slice()));
+
+    Ok((group_key, resp))
+}
+
+#[inline(never)]
+fn parse_tx(buffer: &Buffer) -> Result<Tx, AppSW> {
+    zlog_stack("start parse_tx round3\0");
+
+    let mut tx_pos: usize = 0;
+
+    let identity_index = buffer.get_element(tx_pos)?;
+    tx_pos += 1;
+
+    // Round 1 public packages
+    let elements = buffer.get_element(tx_pos)?;
+    tx_pos += 1;
+
+    let len = buffer.get_u16(tx_pos)?;
+    tx_pos += 2;
+
+    let mut round_1_public_packages = Vec::with_capacity(elements as usize);
+    for _i in 0..elements {
+        let data = buffer.get_slice(tx_pos, tx_pos + len)?;
+        let public_package =
+            PublicPackage::deserialize_from(data).map_err(|_| AppSW::InvalidPublicPackage)?;
+        tx_pos += len;
+
+        round_1_public_packages.push(public_package);
+    }
+
+    // Round 2 public packages
+    let elements = buffer.get_element(tx_pos)?;
+    tx_pos += 1;
+
+    let len = buffer.get_u16(tx_pos)?;
+    tx_pos += 2;
+
+    let mut round_2_public_packages = Vec::with_capacity(elements as usize);
+    for _i in 0..elements {
+        let data = buffer.get_slice(tx_pos, tx_pos + len)?;
+        let public_package = CombinedPublicPackage::deserialize_from(data)
+            .map_err(|_| AppSW::InvalidPublicPackage)?;
+        tx_pos += len;
+
+        round_2_public_packages.push(public_package);
+    }
+
+    // round 2 secret pkg
+    let len = buffer.get_u16(tx_pos)?;
+    tx_pos += 2;
+
+    let round_2_secret_package = buffer.get_slice(tx_pos, tx_pos + len)?.to_vec();
+    tx_pos += len;
+
+    if tx_pos != buffer.pos {
+        return Err(AppSW::InvalidPayload);
+    }
+
+    zlog_stack("done parse_tx round3\0");
+
+    Ok(Tx {
+        identity_index,
+        round_1_public_packages,
+        round_2_public_packages,
+        round_2_secret_package,
+    })
+}
+
+/// Round 1 and round 2 packages must come from the participants of the DKG we started on round 1.
+/// Both sets include our own package. Returns the min signers and the number of participants
+/// of the DKG session.
+#[inline(never)]
+fn check_session(dkg_keys: &DkgKeys, tx: &Tx) -> Result<(u8, u8), AppSW> {
+    zlog_stack("start check_session round3\0");
+
+    let identities: Vec<_> = tx
+        .round_1_public_packages
+        .iter()
+        .map(|p| p.identity().serialize())
+        .collect();
+    let session = check_dkg_session(
+        dkg_keys,
+        tx.identity_index,
+        identities.iter().map(|i| i.as_slice()).collect(),
+    )?;
+    let participants = identities.len() as u8;
+
+    let identities: Vec<_> = tx
+        .round_2_public_packages
+        .iter()
+        .map(|p| p.sender_identity().serialize())
+        .collect();
+    check_dkg_session(
+        dkg_keys,
+        tx.identity_index,
+        identities.iter().map(|i| i.as_slice()).collect(),
+    )?;
+
+    Ok((session.min_signers, participants))
+}
+
+#[inline(never)]
+fn compute_dkg_round_3(
+    tx: &Tx,
+) -> Result<(KeyPackage, FrostPublicKeyPackage, GroupSecretKey), AppSW> {
+    zlog_stack("start compute_dkg_round_3\0");
+
+    let secret = compute_dkg_secret(tx.identity_index);
+
+    let (key_package, public_key_package, group_secret_key) = dkg::round3::round3(
+        &secret,
+        &tx.round_2_secret_package,
+        &tx.round_1_public_packages,
+        &tx.round_2_public_packages,
+    )
+    .map_err(|_| AppSW::DkgRound3Fail)?;
+
+    Ok((
+        key_package,
+        public_key_package.frost_public_key_package().clone(),
+        group_secret_key,
+    ))
+}
@@ -15,40 +15,49 @@
  *  limitations under the License.
  *****************************************************************************/
 
+use super::dkg_round_3::{round_3_result, save_round_3};
 use crate::accumulator::accumulate_data;
-use crate::app_ui::run_action::ui_review_dkg_round3;
+use crate::app_ui::run_action::{ui_review_dkg_round3, DkgAccountSummary};
 use crate::bolos::zlog_stack;
 use crate::context::TxContext;
-use crate::crypto::compute_dkg_secret;
+use crate::crypto::{
+    check_dkg_session, compute_dkg_secret, dkg_session_fingerprint, group_key_fingerprint,
+};
 use crate::nvm::buffer::Buffer;
 use crate::nvm::dkg_keys::DkgKeys;
+use crate::parser::PUBLIC_ADDRESS_SIZE;
 use crate::AppSW;
 use alloc::vec::Vec;
-use core::ptr;
 use ironfish_frost::dkg;
 use ironfish_frost::dkg::group_key::GroupSecretKey;
+use ironfish_frost::dkg::round1::PublicPackage;
 use ironfish_frost::error::IronfishFrostError;
 use ironfish_frost::frost::keys::KeyPackage;
 use ironfish_frost::frost::keys::PublicKeyPackage as FrostPublicKeyPackage;
 use ledger_device_sdk::io::Comm;
 
-pub struct MinTx {
+// Packages are read straight from the NVM buffer, instead of copying them to RAM,
+// so the heap is left for ironfish-frost to compute round 3.
+pub struct MinTx<'a> {
     identity_index: u8,
-    round_1_packages: Vec<Vec<u8>>,
-    round_2_packages: Vec<Vec<u8>>,
-    round_2_secret_package: Vec<u8>,
-    participants: Vec<Vec<u8>>,
-    gsk_bytes: Vec<Vec<u8>>,
+    round_1_packages: Vec<&'a [u8]>,
+    round_2_packages: Vec<&'a [u8]>,
+    round_2_secret_package: &'a [u8],
+    participants: Vec<&'a [u8]>,
+    gsk_bytes: Vec<&'a [u8]>,
 }
 
 #[inline(never)]
 pub fn handler_dkg_round_3_min(
     comm: &mut Comm,
     chunk: u8,
+    slot: u8,
     ctx: &mut TxContext,
 ) -> Result<(), AppSW> {
     zlog_stack("start handler_dkg_round_3\0");
 
+    let dkg_keys = DkgKeys::new(slot)?;
+
     accumulate_data(comm, chunk, ctx)?;
     if !ctx.done {
         return Ok(());
@@ -57,35 +66,53 @@ pub fn handler_dkg_round_3_min(
     // Try to deserialize the transaction
     let min_tx = parse_tx_min(&ctx.buffer)?;
 
-    // As we are running the minimum version of round 3, the identity of the current participant is not included
-    // The same happens to round 1 and round 2 public packages.
-    if !ui_review_dkg_round3(
-        min_tx.identity_index,
-        (min_tx.round_1_packages.len() + 1) as u8,
-        (min_tx.round_2_packages.len() + 1) as u8,
-        (min_tx.participants.len() + 1) as u8,
-        min_tx.gsk_bytes.len() as u8,
-    )? {
-        return Err(AppSW::Deny);
-    }
+    let own_identity = compute_dkg_secret(min_tx.identity_index)
+        .to_identity()
+        .serialize();
+    let min_signers = check_session_min(&dkg_keys, &min_tx, &own_identity)?;
 
-    let (mut key_package, public_key_package, mut group_secret_key) =
-        compute_dkg_round_3_min(&min_tx).map_err(|_| AppSW::DkgRound3Fail)?;
-    drop(min_tx);
+    let mut identities: Vec<&[u8]> = min_tx.participants.clone();
+    identities.push(own_identity.as_slice());
+    let session_fingerprint = dkg_session_fingerprint(min_signers, identities);
 
-    DkgKeys.save_keys(&key_package, public_key_package, &group_secret_key)?;
+    // Keys are computed before asking the user, so the resulting group key can be displayed.
+    // Nothing is saved unless the operation is approved.
+    let (key_package, public_key_package, group_secret_key) =
+        compute_dkg_round_3_min(&min_tx).map_err(|_| AppSW::DkgRound3Fail)?;
 
-    // Zero out memory for the response data
-    unsafe {
-        ptr::write_bytes(&mut group_secret_key as *mut GroupSecretKey, 0, 1);
-        ptr::write_bytes(&mut key_package as *mut KeyPackage, 0, 1);
-    }
+    // As we are running the minimum version of round 3, the identity of the current participant is not included
+    // The same happens to round 1 and round 2 public packages.
+    let approved = match round_3_result(&public_key_package, &group_secret_key) {
+        Ok((group_key, resp)) => ui_review_dkg_round3(
+            min_tx.identity_index,
+            (min_tx.round_1_packages.len() + 1) as u8,
+            (min_tx.round_2_packages.len() + 1) as u8,
+            (min_tx.participants.len() + 1) as u8,
+            Some(min_tx.gsk_bytes.len() as u8),
+            &DkgAccountSummary {
+                session_fingerprint: &session_fingerprint,
+                group_key_fingerprint: &group_key_fingerprint(&group_key),
+                public_address: &resp[..PUBLIC_ADDRESS_SIZE],
+            },
+        )
+        .map(|approved| approved.then_some(resp)),
+        Err(e) => Err(e),
+    };
+    drop(min_tx);
 
-    Ok(())
+    save_round_3(
+        comm,
+        ctx,
+        &dkg_keys,
+        approved,
+        key_package,
+        public_key_package,
+        group_secret_key,
+    )
 }
 
 #[inline(never)]
-fn parse_tx_min(buffer: &Buffer) -> Result<MinTx, AppSW> {
+fn parse_tx_min(buffer: &Buffer) -> Result<MinTx<'_>, AppSW> {
     zlog_stack("start parse_tx_min round3\0");
 
     let mut tx_pos: usize = 0;
@@ -107,7 +134,7 @@ fn parse_tx_min(buffer: &Buffer) -> Result<MinTx, AppSW> {
         tx_pos += len;
 
         zlog_stack("push parse_round_1 - e\0");
-        round_1_packages.push(package.to_vec());
+        round_1_packages.push(package);
         zlog_stack("done parse_round_1 - e\0");
     }
 
@@ -125,7 +152,7 @@ fn parse_tx_min(buffer: &Buffer) -> Result<MinTx, AppSW> {
         tx_pos += len;
 
         zlog_stack("push parse_round_2 - e\0");
-        round_2_packages.push(r2_package.to_vec());
+        round_2_packages.push(r2_package);
         zlog_stack("done parse_round_2 - e\0");
     }
 
@@ -133,8 +160,7 @@ fn parse_tx_min(buffer: &Buffer) -> Result<MinTx, AppSW> {
     let len = buffer.get_u16(tx_pos)?;
     tx_pos += 2;
 
-    let round_2_secret_package_slice = buffer.get_slice(tx_pos, tx_pos + len)?;
-    let round_2_secret_package = round_2_secret_package_slice.to_vec();
+    let round_2_secret_package = buffer.get_slice(tx_pos, tx_pos + len)?;
     tx_pos += len;
 
     // participants
@@ -151,7 +177,7 @@ fn parse_tx_min(buffer: &Buffer) -> Result<MinTx, AppSW> {
         tx_pos += len;
 
         zlog_stack("push parse participants - e\0");
-        participants.push(participant.to_vec());
+        participants.push(participant);
         zlog_stack("done parse participants - e\0");
     }
 
@@ -169,7 +195,7 @@ fn parse_tx_min(buffer: &Buffer) -> Result<MinTx, AppSW> {
         tx_pos += len;
 
         zlog_stack("push parse sgk - e\0");
-        gsk_bytes.push(gsk.to_vec());
+        gsk_bytes.push(gsk);
         zlog_stack("done parse gsk - e\0");
     }
 
@@ -189,26 +215,52 @@ fn parse_tx_min(buffer: &Buffer) -> Result<MinTx, AppSW> {
     })
 }
 
+/// Participants and round 1 packages must match the DKG we started on round 1.
+/// Our own identity is not included on any of them, so we add it back.
+/// Returns the min signers of the DKG session.
+#[inline(never)]
+fn check_session_min(
+    dkg_keys: &DkgKeys,
+    min_tx: &MinTx<'_>,
+    own_identity: &[u8],
+) -> Result<u8, AppSW> {
+    zlog_stack("start check_session_min\0");
+
+    let mut identities: Vec<&[u8]> = min_tx.participants.clone();
+    identities.push(own_identity);
+    let session = check_dkg_session(dkg_keys, min_tx.identity_index, identities)?;
+
+    let round_1_identities = min_tx
+        .round_1_packages
+        .iter()
+        .map(|p| {
+            PublicPackage::deserialize_from(*p)
+                .map(|p| p.identity().serialize())
+                .map_err(|_| AppSW::InvalidPublicPackage)
+        })
+        .collect::<Result<Vec<_>, AppSW>>()?;
+
+    let mut identities: Vec<&[u8]> = round_1_identities.iter().map(|i| i.as_slice()).collect();
+    identities.push(own_identity);
+    check_dkg_session(dkg_keys, min_tx.identity_index, identities)?;
+
+    Ok(session.min_signers)
+}
+
 #[inline(never)]
 fn compute_dkg_round_3_min(
-    min_tx: &MinTx,
+    min_tx: &MinTx<'_>,
 ) -> Result<(KeyPackage, FrostPublicKeyPackage, GroupSecretKey), IronfishFrostError> {
     zlog_stack("start compute_dkg_round_3\0");
 
     let secret = compute_dkg_secret(min_tx.identity_index);
 
-    let p = min_tx.participants.iter().map(|p| p.as_slice()).collect();
-    let r1 = min_tx
-        .round_1_packages
-        .iter()
-        .map(|r| r.as_slice())
-        .collect();
-    let r2 = min_tx
-        .round_2_packages
-        .iter()
-        .map(|r| r.as_slice())
-        .collect();
-    let gsk = min_tx.gsk_bytes.iter().map(|g| g.as_slice()).collect();
-
-    dkg::round3::round3_min(&secret, p, &min_tx.round_2_secret_package, r1, r2, gsk)
+    dkg::round3::round3_min(
+        &secret,
+        min_tx.participants.clone(),
+        min_tx.round_2_secret_package,
+        min_tx.round_1_packages.clone(),
+        min_tx.round_2_packages.clone(),
+        min_tx.gsk_bytes.clone(),
+    )
 }
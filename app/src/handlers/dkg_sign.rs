@@ -15,49 +15,126 @@
  *  limitations under the License.
  *****************************************************************************/
 use crate::accumulator::accumulate_data;
+use crate::app_ui::run_action::ui_review_dkg_sign;
 use crate::bolos::zlog_stack;
 use crate::context::TxContext;
+use crate::crypto::verify_randomized_signature_share;
 use crate::ironfish::constants::TX_HASH_LEN;
 use crate::nvm::buffer::Buffer;
 use crate::nvm::dkg_keys::DkgKeys;
-use crate::nvm::get_and_clear_tx_hash;
+use crate::nvm::get_and_clear_reviewed_tx;
+use crate::nvm::sign_history::SignHistory;
+use crate::utils::fingerprint;
 use crate::utils::response::save_result;
 use crate::AppSW;
+use alloc::string::String;
+use alloc::vec::Vec;
 use ironfish_frost::frost::keys::KeyPackage;
 use ironfish_frost::frost::round1::SigningNonces;
 use ironfish_frost::frost::round2;
+use ironfish_frost::frost::round2::SignatureShare;
 use ironfish_frost::nonces::deterministic_signing_nonces;
+use ironfish_frost::participant::Identity;
 use ironfish_frost::{frost::Randomizer, frost::SigningPackage};
 use ledger_device_sdk::io::Comm;
 
 #[inline(never)]
-pub fn handler_dkg_sign(comm: &mut Comm, chunk: u8, ctx: &mut TxContext) -> Result<(), AppSW> {
+pub fn handler_dkg_sign(
+    comm: &mut Comm,
+    chunk: u8,
+    slot: u8,
+    ctx: &mut TxContext,
+) -> Result<(), AppSW> {
     zlog_stack("start handler_dkg_sign\0");
 
+    let dkg_keys = DkgKeys::new(slot)?;
+
     accumulate_data(comm, chunk, ctx)?;
     if !ctx.done {
         return Ok(());
     }
 
-    let (frost_signing_package, randomizer, tx_hash) = parse_tx(&ctx.buffer)?;
+    let (frost_signing_package, randomizer, raw_randomizer, tx_hash) = parse_tx(&ctx.buffer)?;
 
     // By this point, the transaction should have already been reviewed.
     // Before proceeding, we need to ensure that the transaction was approved.
     // The transaction hash must be available and it should match the hash we received.
-    let current_hash = get_and_clear_tx_hash().ok_or(AppSW::InvalidTxHash)?;
+    let reviewed_tx = get_and_clear_reviewed_tx().ok_or(AppSW::InvalidTxHash)?;
+    let current_hash = reviewed_tx.hash;
 
-    if current_hash != tx_hash {
+    if current_hash != tx_hash || reviewed_tx.slot != slot {
         zlog_stack("tx hash mismatch\0");
         return Err(AppSW::InvalidTxHash);
     }
 
-    let key_package = DkgKeys.load_key_package()?;
-    let nonces = generate_nonces(&key_package, tx_hash)?;
+    // The randomizer must be the public key randomness of the reviewed transaction
+    if reviewed_tx.pubkey_randomness != raw_randomizer {
+        zlog_stack("randomizer mismatch\0");
+        return Err(AppSW::InvalidRandomizer);
+    }
+
+    // The signing package must commit to the very same message we reviewed,
+    // otherwise the host could get a signature share over a different transaction
+    if frost_signing_package.message()[..] != current_hash[..] {
+        zlog_stack("signing package message mismatch\0");
+        return Err(AppSW::InvalidSigningMessage);
+    }
+
+    let key_package = dkg_keys.load_key_package()?;
+    let identities = dkg_keys.load_identities()?;
+    let nonces = generate_nonces(&key_package, tx_hash, &identities);
+
+    // Our commitment in the signing package must be the one derived from
+    // the deterministic nonces we are about to use
+    let own_commitments = frost_signing_package
+        .signing_commitments()
+        .get(key_package.identifier())
+        .ok_or(AppSW::InvalidSigningCommitment)?;
+
+    if own_commitments != nonces.commitments() {
+        zlog_stack("own commitment mismatch\0");
+        return Err(AppSW::InvalidSigningCommitment);
+    }
+
+    // Let the user check who is taking part in this signing session
+    let signers = signing_participants(&frost_signing_package, &identities)?;
+    let min_signers = dkg_keys.load_min_signers()?;
+    let own_index = identities
+        .iter()
+        .position(|identity| identity.to_frost_identifier() == *key_package.identifier())
+        .ok_or(AppSW::InvalidIdentity)?;
+
+    if signers.len() < min_signers {
+        zlog_stack("not enough signers\0");
+        return Err(AppSW::InvalidSigningPackage);
+    }
+
+    if !ui_review_dkg_sign(&signers, own_index as u8, min_signers as u8)? {
+        return Err(AppSW::Deny);
+    }
+
+    // Our nonces only depend on the tx hash, so a different signing package for
+    // an already signed hash would reuse them under another challenge
+    let serialized_signing_package = frost_signing_package
+        .serialize()
+        .map_err(|_| AppSW::InvalidSigningPackage)?;
+    let signing_package_digest =
+        SignHistory::signing_package_digest(serialized_signing_package.as_slice());
+    SignHistory.check_and_record(slot, tx_hash, &signing_package_digest)?;
 
     zlog_stack("start signing\0");
     let signature = round2::sign(&frost_signing_package, &nonces, &key_package, randomizer)
         .map_err(|_| AppSW::TxSignFail)?;
 
+    // Never release a share that does not verify, a faulty share could leak our key share
+    check_signature_share(
+        &dkg_keys,
+        &frost_signing_package,
+        &signature,
+        &key_package,
+        raw_randomizer,
+    )?;
+
     zlog_stack("unwrap sig result\0");
     let resp = signature.serialize();
 
@@ -67,7 +144,7 @@ pub fn handler_dkg_sign(comm: &mut Comm, chunk: u8, ctx: &mut TxContext) -> Resu
 }
 
 #[inline(never)]
-fn parse_tx(buffer: &Buffer) -> Result<(SigningPackage, Randomizer, &[u8]), AppSW> {
+fn parse_tx(buffer: &Buffer) -> Result<(SigningPackage, Randomizer, &[u8], &[u8]), AppSW> {
     zlog_stack("start parse_tx\0");
 
     let mut tx_pos = 0;
@@ -75,8 +152,9 @@ fn parse_tx(buffer: &Buffer) -> Result<(SigningPackage, Randomizer, &[u8]), AppS
     let pk_randomness_len = buffer.get_u16(tx_pos)?;
     tx_pos += 2;
 
-    let data = buffer.get_slice(tx_pos, tx_pos + pk_randomness_len)?;
-    let randomizer = Randomizer::deserialize(data).map_err(|_| AppSW::InvalidRandomizer)?;
+    let raw_randomizer = buffer.get_slice(tx_pos, tx_pos + pk_randomness_len)?;
+    let randomizer =
+        Randomizer::deserialize(raw_randomizer).map_err(|_| AppSW::InvalidRandomizer)?;
     tx_pos += pk_randomness_len;
 
     let frost_signing_package_len = buffer.get_u16(tx_pos)?;
@@ -94,16 +172,79 @@ fn parse_tx(buffer: &Buffer) -> Result<(SigningPackage, Randomizer, &[u8]), AppS
         return Err(AppSW::InvalidPayload);
     }
 
-    Ok((frost_signing_package, randomizer, tx_hash))
+    Ok((frost_signing_package, randomizer, raw_randomizer, tx_hash))
+}
+
+#[inline(never)]
+fn check_signature_share(
+    dkg_keys: &DkgKeys,
+    signing_package: &SigningPackage,
+    signature_share: &SignatureShare,
+    key_package: &KeyPackage,
+    raw_randomizer: &[u8],
+) -> Result<(), AppSW> {
+    zlog_stack("start check_signature_share\0");
+
+    let public_key_package = dkg_keys.load_frost_public_key_package()?;
+
+    // Our verifying share is taken from the group public package,
+    // and it must match the one in our own key package
+    let verifying_share = public_key_package
+        .verifying_shares()
+        .get(key_package.identifier())
+        .ok_or(AppSW::InvalidSignatureShare)?;
+
+    if verifying_share != key_package.verifying_share()
+        || public_key_package.verifying_key() != key_package.verifying_key()
+    {
+        zlog_stack("verifying share mismatch\0");
+        return Err(AppSW::InvalidSignatureShare);
+    }
+
+    // The share was produced under the randomized keys, so verify it against them
+    let raw_randomizer: &[u8; 32] = raw_randomizer
+        .try_into()
+        .map_err(|_| AppSW::InvalidRandomizer)?;
+
+    verify_randomized_signature_share(
+        key_package.identifier(),
+        signature_share,
+        signing_package,
+        &public_key_package,
+        raw_randomizer,
+    )
+}
+
+#[inline(never)]
+fn generate_nonces(
+    key_package: &KeyPackage,
+    tx_hash: &[u8],
+    identities: &[Identity],
+) -> SigningNonces {
+    deterministic_signing_nonces(key_package.signing_share(), tx_hash, identities)
 }
 
+/// Maps the participants with commitments in the signing package to their position
+/// in the stored identities, along with the fingerprint of their identity
 #[inline(never)]
-fn generate_nonces(key_package: &KeyPackage, tx_hash: &[u8]) -> Result<SigningNonces, AppSW> {
-    let identities = DkgKeys.load_identities()?;
-
-    Ok(deterministic_signing_nonces(
-        key_package.signing_share(),
-        tx_hash,
-        &identities,
-    ))
+fn signing_participants(
+    signing_package: &SigningPackage,
+    identities: &[Identity],
+) -> Result<Vec<(u8, String)>, AppSW> {
+    zlog_stack("start signing_participants\0");
+
+    signing_package
+        .signing_commitments()
+        .keys()
+        .map(|identifier| {
+            identities
+                .iter()
+                .position(|identity| identity.to_frost_identifier() == *identifier)
+                .map(|index| {
+                    let identity = identities[index].serialize();
+                    (index as u8, fingerprint(&[identity.as_ref()]))
+                })
+                .ok_or(AppSW::UnknownSigner)
+        })
+        .collect()
 }
@@ -0,0 +1,84 @@
+/*****************************************************************************
+ *   Ledger App Ironfish Rust.
+ *   (c) 2023 Ledger SAS.
+ *
+ *  Licensed under the Apache License, Version 2.0 (the "License");
+ *  you may not use this file except in compliance with the License.
+ *  You may obtain a copy of the License at
+ *
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *  Unless required by applicable law or agreed to in writing, software
+ *  distributed under the License is distributed on an "AS IS" BASIS,
+ *  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *  See the License for the specific language governing permissions and
+ *  limitations under the License.
+ *****************************************************************************/
+
+use crate::accumulator::accumulate_data;
+use crate::app_ui::run_action::ui_review_verify_backup;
+use crate::bolos::zlog_stack;
+use crate::context::TxContext;
+use crate::handlers::{open_any_backup, stored_account_address};
+use crate::nvm::dkg_keys::DkgKeys;
+use crate::AppSW;
+use ledger_device_sdk::io::Comm;
+
+/// How the account in the backup relates to the one stored in the slot
+#[derive(Clone, Copy)]
+enum StoredAccount {
+    None = 0,
+    Same = 1,
+    Different = 2,
+}
+
+/// Checks that a backup decrypts and holds usable keys, as a restore would, without writing
+/// anything to the slot.
+#[inline(never)]
+pub fn handler_dkg_verify_backup(
+    comm: &mut Comm,
+    chunk: u8,
+    slot: u8,
+    ctx: &mut TxContext,
+) -> Result<(), AppSW> {
+    zlog_stack("start handler_dkg_verify_backup\0");
+
+    let dkg_keys = DkgKeys::new(slot)?;
+
+    accumulate_data(comm, chunk, ctx)?;
+    if !ctx.done {
+        return Ok(());
+    }
+
+    let payload = ctx.buffer.get_slice(0, ctx.buffer.pos)?;
+    let account = open_any_backup(payload)?;
+
+    let stored_account = match stored_account_address(&dkg_keys)? {
+        None => StoredAccount::None,
+        Some(address) if address[..] == account.public_address[..] => StoredAccount::Same,
+        Some(_) => StoredAccount::Different,
+    };
+    let stored_account_label = match stored_account {
+        StoredAccount::None => "None",
+        StoredAccount::Same => "Same account",
+        StoredAccount::Different => "Other account",
+    };
+
+    if !ui_review_verify_backup(
+        &account.public_address,
+        account.participants,
+        account.min_signers,
+        stored_account_label,
+    )? {
+        return Err(AppSW::Deny);
+    }
+
+    comm.append(&[
+        stored_account as u8,
+        account.min_signers,
+        account.participants,
+    ]);
+    comm.append(&account.public_address);
+
+    Ok(())
+}
@@ -20,17 +20,25 @@ use crate::context::TxContext;
 use core::mem::MaybeUninit;
 
 use crate::app_ui::run_action::ui_review_transaction;
-use crate::crypto::derive_multisig_account;
+use crate::crypto::{derive_multisig_account, multisig_to_key_type};
+use crate::nvm::dkg_keys::DkgKeys;
 use crate::utils::response::save_result;
 use crate::{AppSW, FromBytes, Transaction};
 use ledger_device_sdk::io::Comm;
 
 #[inline(never)]
-pub fn handler_review_tx(comm: &mut Comm, chunk: u8, ctx: &mut TxContext) -> Result<(), AppSW> {
-    use crate::nvm::set_tx_hash;
+pub fn handler_review_tx(
+    comm: &mut Comm,
+    chunk: u8,
+    slot: u8,
+    ctx: &mut TxContext,
+) -> Result<(), AppSW> {
+    use crate::nvm::set_reviewed_tx;
 
     zlog_stack("start handler_review_tx\0");
 
+    let dkg_keys = DkgKeys::new(slot)?;
+
     accumulate_data(comm, chunk, ctx)?;
     if !ctx.done {
         return Ok(());
@@ -49,15 +57,19 @@ pub fn handler_review_tx(comm: &mut Comm, chunk: u8, ctx: &mut TxContext) -> Res
     let hash = tx.hash();
 
     // Get outgoing viewing key
-    let account_keys = derive_multisig_account(None)?;
+    let account_keys = derive_multisig_account(dkg_keys.data())?;
+    let from = hex::encode(multisig_to_key_type(&account_keys, 0)?);
+
+    // Make sure the transaction spends from our multisig account
+    tx.verify_randomized_public_key(&account_keys.view_key.authorizing_key)?;
 
     // review transaction
-    if !ui_review_transaction(&tx, &account_keys.outgoing_viewing_key)? {
+    if !ui_review_transaction(&tx, &account_keys.outgoing_viewing_key, &from)? {
         return Err(AppSW::Deny);
     }
 
-    // Save transaction hash in memory
-    set_tx_hash(hash);
+    // Save transaction hash and randomness in memory
+    set_reviewed_tx(slot, hash, *tx.pubkey_randomness());
     zlog_stack("tx_hash set***\0");
 
     let total_chunks = save_result(ctx, hash.as_slice())?;
@@ -5,19 +5,83 @@ use ledger_device_sdk::io::ApduHeader;
 /// Possible input commands received through APDUs.
 pub enum Instruction {
     GetVersion,
-    DkgGetIdentity { review: bool },
-    DkgGetPublicPackage,
-    DkgGetIdentities,
-    DkgRound1 { chunk: u8 },
-    DkgRound2 { chunk: u8 },
-    DkgRound3Min { chunk: u8 },
-    DkgCommitments { chunk: u8 },
-    DkgSign { chunk: u8 },
-    DkgGetKeys { key_type: u8, review: bool },
-    DkgBackupKeys,
-    DkgRestoreKeys { chunk: u8 },
-    GetResult { chunk: u8 },
-    ReviewTx { chunk: u8 },
+    DkgGetIdentity {
+        review: bool,
+    },
+    DkgGetPublicPackage {
+        slot: u8,
+    },
+    DkgGetIdentities {
+        slot: u8,
+    },
+    DkgRound1 {
+        chunk: u8,
+        slot: u8,
+    },
+    DkgRound2 {
+        chunk: u8,
+        slot: u8,
+    },
+    DkgRound3Min {
+        chunk: u8,
+        slot: u8,
+    },
+    DkgCommitments {
+        chunk: u8,
+        slot: u8,
+    },
+    DkgSign {
+        chunk: u8,
+        slot: u8,
+    },
+    DkgGetKeys {
+        key_type: u8,
+        review: bool,
+        slot: u8,
+    },
+    DkgBackupKeys {
+        slot: u8,
+    },
+    DkgRestoreKeys {
+        chunk: u8,
+        slot: u8,
+    },
+    GetResult {
+        chunk: u8,
+    },
+    ReviewTx {
+        chunk: u8,
+        slot: u8,
+    },
+    DkgAggregate {
+        chunk: u8,
+        slot: u8,
+    },
+    DkgRound3 {
+        chunk: u8,
+        slot: u8,
+    },
+    DkgListAccounts,
+    DkgDeleteKeys {
+        slot: u8,
+    },
+    DkgGetStatus {
+        slot: u8,
+    },
+    DkgAbort {
+        slot: u8,
+    },
+    DkgBackupKeysCosigners {
+        slot: u8,
+    },
+    DkgReencryptBackup {
+        chunk: u8,
+        slot: u8,
+    },
+    DkgVerifyBackup {
+        chunk: u8,
+        slot: u8,
+    },
 }
 
 #[cfg(feature = "ledger")]
@@ -41,24 +105,68 @@ impl TryFrom<ApduHeader> for Instruction {
             (0x10, 0..=1, 0) => Ok(Instruction::DkgGetIdentity {
                 review: value.p1 == 1,
             }),
-            (0x11, 0..=2, 0) => Ok(Instruction::DkgRound1 { chunk: value.p1 }),
-            (0x12, 0..=2, 0) => Ok(Instruction::DkgRound2 { chunk: value.p1 }),
-            (0x13, 0..=2, 0) => Ok(Instruction::DkgRound3Min { chunk: value.p1 }),
-            (0x14, 0..=2, 0) => Ok(Instruction::DkgCommitments { chunk: value.p1 }),
-            (0x15, 0..=2, 0) => Ok(Instruction::DkgSign { chunk: value.p1 }),
-            (0x16, 0..=1, 0..=3) => Ok(Instruction::DkgGetKeys {
+            (0x11, 0..=2, _) => Ok(Instruction::DkgRound1 {
+                chunk: value.p1,
+                slot: value.p2,
+            }),
+            (0x12, 0..=2, _) => Ok(Instruction::DkgRound2 {
+                chunk: value.p1,
+                slot: value.p2,
+            }),
+            (0x13, 0..=2, _) => Ok(Instruction::DkgRound3Min {
+                chunk: value.p1,
+                slot: value.p2,
+            }),
+            (0x14, 0..=2, _) => Ok(Instruction::DkgCommitments {
+                chunk: value.p1,
+                slot: value.p2,
+            }),
+            (0x15, 0..=2, _) => Ok(Instruction::DkgSign {
+                chunk: value.p1,
+                slot: value.p2,
+            }),
+            // P2 holds the key type on its low nibble, and the account slot on its high nibble
+            (0x16, 0..=1, p2) if p2 & 0x0f <= 3 => Ok(Instruction::DkgGetKeys {
                 review: value.p1 == 1,
-                key_type: value.p2,
+                key_type: p2 & 0x0f,
+                slot: p2 >> 4,
+            }),
+            (0x17, 0, _) => Ok(Instruction::DkgGetIdentities { slot: value.p2 }),
+            (0x18, 0, _) => Ok(Instruction::DkgGetPublicPackage { slot: value.p2 }),
+            (0x19, 0, _) => Ok(Instruction::DkgBackupKeys { slot: value.p2 }),
+            (0x1a, 0..=2, _) => Ok(Instruction::DkgRestoreKeys {
+                chunk: value.p1,
+                slot: value.p2,
             }),
-            (0x17, 0, 0) => Ok(Instruction::DkgGetIdentities),
-            (0x18, 0, 0) => Ok(Instruction::DkgGetPublicPackage),
-            (0x19, 0, 0) => Ok(Instruction::DkgBackupKeys),
-            (0x1a, 0..=2, 0) => Ok(Instruction::DkgRestoreKeys { chunk: value.p1 }),
             (0x1b, 0..=255, 0) => Ok(Instruction::GetResult { chunk: value.p1 }),
-            (0x1c, 0..=2, 0) => Ok(Instruction::ReviewTx { chunk: value.p1 }),
+            (0x1c, 0..=2, _) => Ok(Instruction::ReviewTx {
+                chunk: value.p1,
+                slot: value.p2,
+            }),
+            (0x1d, 0..=2, _) => Ok(Instruction::DkgAggregate {
+                chunk: value.p1,
+                slot: value.p2,
+            }),
+            (0x1e, 0..=2, _) => Ok(Instruction::DkgRound3 {
+                chunk: value.p1,
+                slot: value.p2,
+            }),
+            (0x1f, 0, 0) => Ok(Instruction::DkgListAccounts),
+            (0x20, 0, _) => Ok(Instruction::DkgDeleteKeys { slot: value.p2 }),
+            (0x21, 0, _) => Ok(Instruction::DkgGetStatus { slot: value.p2 }),
+            (0x22, 0, _) => Ok(Instruction::DkgAbort { slot: value.p2 }),
+            (0x23, 0, _) => Ok(Instruction::DkgBackupKeysCosigners { slot: value.p2 }),
+            (0x24, 0..=2, _) => Ok(Instruction::DkgReencryptBackup {
+                chunk: value.p1,
+                slot: value.p2,
+            }),
+            (0x25, 0..=2, _) => Ok(Instruction::DkgVerifyBackup {
+                chunk: value.p1,
+                slot: value.p2,
+            }),
             // Any supported ins with wrong p1 p2 should fall here
             (0x00, _, _) => Err(AppSW::WrongP1P2),
-            (0x11..=0x1c, _, _) => Err(AppSW::WrongP1P2),
+            (0x11..=0x25, _, _) => Err(AppSW::WrongP1P2),
             // Any other value (unsupported ins) should fall here
             (_, _, _) => Err(AppSW::InsNotSupported),
         }
@@ -1,6 +1,7 @@
 pub mod constants;
 pub mod errors;
 pub mod multisig;
+pub mod pedersen_hash;
 pub mod public_address;
 pub mod sapling;
 pub mod view_keys;
@@ -4,7 +4,10 @@ pub const TX_HASH_LEN: usize = 32;
 
 pub const IDENTITY_LEN: usize = 129;
 
-pub const MAX_PARTICIPANTS: u8 = 4;
+pub const MAX_PARTICIPANTS: u8 = 7;
+
+/// Highest index DKG identities can be derived from
+pub const MAX_IDENTITY_INDEX: u8 = 5;
 
 pub const SPENDING_KEY_GENERATOR: AffineNielsPoint = AffinePoint::from_raw_unchecked(
     Fq::from_raw([
@@ -53,7 +56,132 @@ pub const PUBLIC_KEY_GENERATOR: AffineNielsPoint = AffinePoint::from_raw_uncheck
     ]),
 )
 .to_niels();
+
+/// The generator used to randomize note commitments
+pub const NOTE_COMMITMENT_RANDOMNESS_GENERATOR: AffineNielsPoint = AffinePoint::from_raw_unchecked(
+    Fq::from_raw([
+        0xa514_3b34_a8e3_6462,
+        0xf091_9d06_ffb1_ecda,
+        0xa140_9aa1_f33b_ec2c,
+        0x26eb_9f8a_9ec7_2a8c,
+    ]),
+    Fq::from_raw([
+        0xd4fc_6365_796c_77ac,
+        0x96b7_8bea_fa9c_c44c,
+        0x949d_7747_6e26_2c95,
+        0x114b_7501_ad10_4c57,
+    ]),
+)
+.to_niels();
+
+/// The generators used by the pedersen hash, one per segment of 63 chunks
+pub const PEDERSEN_HASH_GENERATORS: [AffineNielsPoint; 6] = [
+    AffinePoint::from_raw_unchecked(
+        Fq::from_raw([
+            0x194e_4292_6f66_1b51,
+            0x2f0c_718f_6f0f_badd,
+            0xb5ea_25de_7ec0_e378,
+            0x73c0_16a4_2ded_9578,
+        ]),
+        Fq::from_raw([
+            0x77bf_abd4_3224_3cca,
+            0xf947_2e8b_c04e_4632,
+            0x79c9_166b_837e_dc5e,
+            0x289e_87a2_d352_1b57,
+        ]),
+    )
+    .to_niels(),
+    AffinePoint::from_raw_unchecked(
+        Fq::from_raw([
+            0xb981_9dc8_2d90_607e,
+            0xa361_ee3f_d48f_df77,
+            0x52a3_5a8c_1908_dd87,
+            0x15a3_6d1f_0f39_0d88,
+        ]),
+        Fq::from_raw([
+            0x7b0d_c53c_4ebf_1891,
+            0x1f3a_beeb_98fa_d3e8,
+            0xf789_1142_c001_d925,
+            0x015d_8c7f_5b43_fe33,
+        ]),
+    )
+    .to_niels(),
+    AffinePoint::from_raw_unchecked(
+        Fq::from_raw([
+            0x76d6_f7c2_b67f_c475,
+            0xbae8_e5c4_6641_ae5c,
+            0xeb69_ae39_f5c8_4210,
+            0x6643_21a5_8246_e2f6,
+        ]),
+        Fq::from_raw([
+            0x80ed_502c_9793_d457,
+            0x8bb2_2a7f_1784_b498,
+            0xe000_a46c_8e8c_e853,
+            0x362e_1500_d24e_ee9e,
+        ]),
+    )
+    .to_niels(),
+    AffinePoint::from_raw_unchecked(
+        Fq::from_raw([
+            0x4c76_7804_c1c4_a2cc,
+            0x7d02_d50e_654b_87f2,
+            0xedc5_f4a9_cff2_9fd5,
+            0x323a_6548_ce9d_9876,
+        ]),
+        Fq::from_raw([
+            0x8471_4bec_a335_70e9,
+            0x5103_afa1_a11f_6a85,
+            0x9107_0acb_d8d9_47b7,
+            0x2f7e_e40c_4b56_cad8,
+        ]),
+    )
+    .to_niels(),
+    AffinePoint::from_raw_unchecked(
+        Fq::from_raw([
+            0x4680_9430_657f_82d1,
+            0xefd5_9313_05f2_f0bf,
+            0x89b6_4b4e_0336_2796,
+            0x3bd2_6660_00b5_4796,
+        ]),
+        Fq::from_raw([
+            0x9996_8299_c365_8aef,
+            0xb3b9_d809_5859_d14c,
+            0x3978_3238_1406_c9e5,
+            0x494b_c521_03ab_9d0a,
+        ]),
+    )
+    .to_niels(),
+    AffinePoint::from_raw_unchecked(
+        Fq::from_raw([
+            0xcb3c_0232_58d3_2079,
+            0x1d9e_5ca2_1135_ff6f,
+            0xda04_9746_d76d_3ee5,
+            0x6344_7b2b_a31b_b28a,
+        ]),
+        Fq::from_raw([
+            0x4360_8211_9f8d_629a,
+            0xa802_00d2_c66b_13a7,
+            0x64cd_b107_0a13_6a28,
+            0x64ec_4689_e8bf_b6e5,
+        ]),
+    )
+    .to_niels(),
+];
+
 /// BLAKE2s Personalization for CRH^ivk = BLAKE2s(ak | nk)
 pub const CRH_IVK_PERSONALIZATION: &[u8; 8] = b"Zcashivk";
 
 pub const SHARED_KEY_PERSONALIZATION: &[u8; 16] = b"Iron Fish Keyenc";
+
+/// BLAKE2s Personalization for Pedersen hash generators.
+pub const PEDERSEN_HASH_GENERATORS_PERSONALIZATION: &[u8; 8] = b"Zcash_PH";
+
+/// The number of 3-bit chunks each pedersen hash generator is used for.
+pub const PEDERSEN_HASH_CHUNKS_PER_GENERATOR: usize = 63;
+
+/// First 64 bytes of the BLAKE2s input during group hash.
+/// This is chosen to be some random string that we couldn't have anticipated when we designed
+/// the algorithm, for rigidity purposes.
+/// We deliberately use an ASCII hex string of 32 bytes here.
+pub const GH_FIRST_BLOCK: &[u8; 64] =
+    b"096b36a5804bfacef1691e173c366a47ff5ba84a44f26ddd7e8d9f79d5b42df0";
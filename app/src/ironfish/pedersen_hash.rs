@@ -0,0 +1,139 @@
+use jubjub::{ExtendedPoint, Fr};
+
+use crate::ironfish::constants::{PEDERSEN_HASH_CHUNKS_PER_GENERATOR, PEDERSEN_HASH_GENERATORS};
+
+#[derive(Copy, Clone)]
+pub enum Personalization {
+    NoteCommitment,
+}
+
+impl Personalization {
+    fn get_bits(&self) -> [bool; 6] {
+        match *self {
+            Personalization::NoteCommitment => [true; 6],
+        }
+    }
+}
+
+/// Sapling pedersen hash over the given bits, as used by ironfish
+/// to compute note commitments.
+///
+/// Input bits are consumed in 3-bit chunks, each one encoding a signed
+/// digit that is accumulated into the scalar applied to the current
+/// generator. A new generator is used every 63 chunks.
+#[inline(never)]
+pub fn pedersen_hash<I>(personalization: Personalization, bits: I) -> ExtendedPoint
+where
+    I: IntoIterator<Item = bool>,
+{
+    let mut bits = personalization.get_bits().into_iter().chain(bits);
+
+    let mut result = ExtendedPoint::identity();
+    let mut generators = PEDERSEN_HASH_GENERATORS.iter();
+
+    loop {
+        let mut acc = Fr::zero();
+        let mut cur = Fr::one();
+        let mut chunks_remaining = PEDERSEN_HASH_CHUNKS_PER_GENERATOR;
+        let mut encountered_bits = false;
+
+        // Grab three bits from the input
+        while let Some(a) = bits.next() {
+            encountered_bits = true;
+
+            let b = bits.next().unwrap_or(false);
+            let c = bits.next().unwrap_or(false);
+
+            // Start computing this portion of the scalar
+            let mut tmp = cur;
+            if a {
+                tmp = tmp.add(&cur);
+            }
+            cur = cur.double(); // 2^1 * cur
+            if b {
+                tmp = tmp.add(&cur);
+            }
+
+            // conditionally negate
+            if c {
+                tmp = tmp.neg();
+            }
+
+            acc = acc.add(&tmp);
+
+            chunks_remaining -= 1;
+
+            if chunks_remaining == 0 {
+                break;
+            } else {
+                cur = cur.double().double().double(); // 2^4 * cur
+            }
+        }
+
+        if !encountered_bits {
+            break;
+        }
+
+        // The input is bounded by the note size, so we never
+        // run out of generators
+        let generator = generators.next().expect("not enough pedersen generators");
+        result += generator * acc;
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod pedersen_hash_test {
+    use super::*;
+    use crate::ironfish::constants::{
+        GH_FIRST_BLOCK, NOTE_COMMITMENT_RANDOMNESS_GENERATOR,
+        PEDERSEN_HASH_GENERATORS_PERSONALIZATION,
+    };
+    use blake2s_simd::Params as Blake2s;
+    use jubjub::{AffineNielsPoint, AffinePoint};
+
+    fn find_group_hash(m: &[u8], personalization: &[u8]) -> AffinePoint {
+        let mut tag = m.to_vec();
+        let i = tag.len();
+        tag.push(0u8);
+
+        loop {
+            let hash = Blake2s::new()
+                .hash_length(32)
+                .personal(personalization)
+                .to_state()
+                .update(GH_FIRST_BLOCK)
+                .update(&tag)
+                .finalize();
+
+            if let Some(p) = Option::<AffinePoint>::from(AffinePoint::from_bytes(*hash.as_array()))
+            {
+                let p = p.mul_by_cofactor();
+                if !bool::from(p.is_identity()) {
+                    return AffinePoint::from(p);
+                }
+            }
+
+            tag[i] += 1;
+        }
+    }
+
+    fn to_affine(point: &AffineNielsPoint) -> AffinePoint {
+        AffinePoint::from(ExtendedPoint::identity() + point)
+    }
+
+    #[test]
+    fn generators() {
+        for (m, generator) in PEDERSEN_HASH_GENERATORS.iter().enumerate() {
+            let expected = find_group_hash(
+                &(m as u32).to_le_bytes(),
+                PEDERSEN_HASH_GENERATORS_PERSONALIZATION,
+            );
+            assert_eq!(to_affine(generator), expected);
+        }
+
+        let expected = find_group_hash(b"r", PEDERSEN_HASH_GENERATORS_PERSONALIZATION);
+        assert_eq!(to_affine(&NOTE_COMMITMENT_RANDOMNESS_GENERATOR), expected);
+    }
+}
@@ -1,6 +1,7 @@
 pub mod buffer;
 pub mod dkg_keys;
 pub mod settings;
+pub mod sign_history;
 
 pub use buffer::*;
 pub use dkg_keys::*;
@@ -12,25 +13,44 @@ use crate::bolos::zlog_stack;
 #[cfg(not(feature = "ledger"))]
 use std::sync::Mutex;
 
+/// The transaction approved by the user on the review step.
+/// Besides its hash, we keep the public key randomness so we only
+/// sign with the randomizer of the reviewed transaction, and the
+/// account slot it was reviewed for.
+#[derive(Clone, Copy)]
+pub(crate) struct ReviewedTx {
+    pub(crate) slot: u8,
+    pub(crate) hash: [u8; 32],
+    pub(crate) pubkey_randomness: [u8; 32],
+}
+
 lazy_static::lazy_static! {
-    static ref GLOBAL: Mutex<Option<[u8; 32]>> = Mutex::new(None);
+    static ref GLOBAL: Mutex<Option<ReviewedTx>> = Mutex::new(None);
 }
 
 // not sure if this is the best place,
-// Function to set the global array
-pub(crate) fn set_tx_hash(data: [u8; 32]) {
-    zlog_stack("set tx hash\0");
+// Function to set the global reviewed tx
+pub(crate) fn set_reviewed_tx(slot: u8, hash: [u8; 32], pubkey_randomness: [u8; 32]) {
+    zlog_stack("set reviewed tx\0");
     let mut global = GLOBAL.lock();
-    global.replace(data);
+    global.replace(ReviewedTx {
+        slot,
+        hash,
+        pubkey_randomness,
+    });
 }
 
-// Function to get and clear the global array
-pub(crate) fn get_and_clear_tx_hash() -> Option<[u8; 32]> {
-    zlog_stack("take and clean tx hash\0");
+// Function to get and clear the global reviewed tx
+pub(crate) fn get_and_clear_reviewed_tx() -> Option<ReviewedTx> {
+    zlog_stack("take and clean reviewed tx\0");
     let mut global = GLOBAL.lock();
 
     // Take the current value, and replace it with zeros
-    let value = global.replace([0; 32]);
+    let value = global.replace(ReviewedTx {
+        slot: 0,
+        hash: [0; 32],
+        pubkey_randomness: [0; 32],
+    });
     // Once memory is zero-ed, let's set it to None
     global.take();
 
@@ -38,9 +58,9 @@ pub(crate) fn get_and_clear_tx_hash() -> Option<[u8; 32]> {
     value
 }
 
-// Function to get
-pub(crate) fn get_tx_hash() -> Option<[u8; 32]> {
+// Function to get the reviewed tx hash, if it was reviewed for the given account slot
+pub(crate) fn get_tx_hash(slot: u8) -> Option<[u8; 32]> {
     zlog_stack("copy tx hash\0");
     let global = GLOBAL.lock();
-    *global
+    global.filter(|tx| tx.slot == slot).map(|tx| tx.hash)
 }
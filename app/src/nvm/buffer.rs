@@ -4,8 +4,17 @@ use ledger_device_sdk::NVMData;
 use nom::number::complete::be_u16;
 
 // This is necessary to store the object in NVM and not in RAM
-// The max data received is round2 for 4 participants, which sends 2250 bytes.
-pub const BUFFER_SIZE: usize = 4000;
+// The max data received is the full round 3 for 7 participants, which sends around 11500 bytes.
+// The buffer is split in pages, so each write only updates the pages it touches,
+// instead of copying the whole buffer to the stack.
+const PAGE_SIZE: usize = 512;
+const PAGES: usize = 32;
+pub const BUFFER_SIZE: usize = PAGE_SIZE * PAGES;
+
+// Pages are aligned to 64 bytes and their size is a multiple of it, so they are laid out
+// contiguously and the buffer can be read as a single slice
+const _: () =
+    assert!(core::mem::size_of::<[AlignedStorage<[u8; PAGE_SIZE]>; PAGES]>() == BUFFER_SIZE);
 
 #[derive(Clone, Copy)]
 pub enum BufferMode {
@@ -13,9 +22,11 @@ pub enum BufferMode {
     Result,
 }
 
+const EMPTY_PAGE: AlignedStorage<[u8; PAGE_SIZE]> = AlignedStorage::new([0u8; PAGE_SIZE]);
+
 #[link_section = ".nvm_data"]
-static mut DATA: NVMData<SafeStorage<[u8; BUFFER_SIZE]>> =
-    NVMData::new(SafeStorage::new([0u8; BUFFER_SIZE]));
+static mut DATA: NVMData<[AlignedStorage<[u8; PAGE_SIZE]>; PAGES]> =
+    NVMData::new([EMPTY_PAGE; PAGES]);
 
 #[derive(Clone, Copy)]
 pub struct Buffer {
@@ -43,25 +54,43 @@ impl Buffer {
     }
 
     #[inline(never)]
-    pub fn is_valid_write(&self) -> Result<(), AppSW> {
-        let buffer = unsafe { DATA.get_mut() };
-        if !buffer.is_valid() {
-            return Err(AppSW::InvalidNVMWrite);
-        }
-
-        Ok(())
+    fn data(&self) -> &[u8] {
+        let pages = unsafe { DATA.get_mut() };
+        unsafe { core::slice::from_raw_parts(pages.as_ptr() as *const u8, BUFFER_SIZE) }
     }
 
+    /// Writes value at index, updating only the pages it spans
     #[inline(never)]
-    pub fn get_mut_ref(&mut self) -> &mut SafeStorage<[u8; BUFFER_SIZE]> {
-        unsafe { DATA.get_mut() }
+    fn write(&self, index: usize, value: &[u8]) -> Result<(), AppSW> {
+        let pages = unsafe { DATA.get_mut() };
+
+        let mut pos = index;
+        let mut remaining = value;
+        while !remaining.is_empty() {
+            let page = pages
+                .get_mut(pos / PAGE_SIZE)
+                .ok_or(AppSW::BufferOutOfBounds)?;
+            let offset = pos % PAGE_SIZE;
+            let len = remaining.len().min(PAGE_SIZE - offset);
+
+            let mut updated_page: [u8; PAGE_SIZE] = *page.get_ref();
+            updated_page[offset..offset + len].copy_from_slice(&remaining[..len]);
+            page.update(&updated_page);
+
+            if page.get_ref()[offset..offset + len] != remaining[..len] {
+                return Err(AppSW::InvalidNVMWrite);
+            }
+
+            pos += len;
+            remaining = &remaining[len..];
+        }
+
+        Ok(())
     }
 
     #[inline(never)]
     pub fn get_element(&self, index: usize) -> Result<u8, AppSW> {
-        let buffer = unsafe { DATA.get_mut() };
-        buffer
-            .get_ref()
+        self.data()
             .get(index)
             .ok_or(AppSW::BufferOutOfBounds)
             .copied()
@@ -69,19 +98,8 @@ impl Buffer {
 
     #[inline(never)]
     pub fn set_element(&self, index: usize, value: u8) -> Result<(), AppSW> {
-        let mut updated_data: [u8; BUFFER_SIZE] = unsafe { *DATA.get_mut().get_ref() };
-
-        updated_data
-            .get_mut(index)
-            .map(|v| *v = value)
-            .ok_or(AppSW::BufferOutOfBounds)?;
-
-        unsafe {
-            DATA.get_mut().update(&updated_data);
-        }
-
-        self.is_valid_write()?;
-        Ok(())
+        self.check_write_pos(index)?;
+        self.write(index, &[value])
     }
 
     #[inline(never)]
@@ -89,15 +107,7 @@ impl Buffer {
         let end_index = index + value.len();
         self.check_write_pos(end_index - 1)?;
 
-        let mut updated_data: [u8; BUFFER_SIZE] = unsafe { *DATA.get_mut().get_ref() };
-
-        // Copy the entire slice at once
-        updated_data[index..end_index].copy_from_slice(value);
-
-        unsafe {
-            DATA.get_mut().update(&updated_data);
-        }
-        self.is_valid_write()?;
+        self.write(index, value)?;
         self.pos += value.len();
         Ok(())
     }
@@ -105,28 +115,23 @@ impl Buffer {
     #[inline(never)]
     pub fn get_slice(&self, start_pos: usize, end_pos: usize) -> Result<&[u8], AppSW> {
         self.check_read_pos_slice(end_pos)?;
-        let buffer = unsafe { DATA.get_mut() };
 
-        Ok(&buffer.get_ref()[start_pos..end_pos])
+        Ok(&self.data()[start_pos..end_pos])
     }
 
     #[inline(never)]
     pub fn get_u16(&self, start_pos: usize) -> Result<usize, AppSW> {
-        let buffer = unsafe { DATA.get_mut() };
-        let buffer_ref = buffer.get_ref();
-
         // Check we are within the read section of the internal buffer
         self.check_read_pos(start_pos + 1)?;
 
-        let input = &buffer_ref[start_pos..];
+        let input = &self.data()[start_pos..];
         let (_, value) = be_u16(input)?;
 
         Ok(value as usize)
     }
 
     pub fn get_full_buffer(&self) -> &[u8] {
-        let buffer = unsafe { DATA.get_mut() };
-        buffer.get_ref().as_slice()
+        self.data()
     }
 
     fn check_read_pos(&self, index: usize) -> Result<(), AppSW> {
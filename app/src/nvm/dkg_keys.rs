@@ -1,5 +1,6 @@
 use crate::bolos::zlog_stack;
-use crate::crypto::{GroupSecretKeyGuard, KeyPackageGuard};
+use crate::crypto::{GroupSecretKeyGuard, KeyPackageGuard, DKG_SESSION_DIGEST_LEN};
+use crate::ironfish::constants::MAX_IDENTITY_INDEX;
 use crate::AppSW;
 use alloc::vec::Vec;
 use ironfish_frost::dkg::group_key::GroupSecretKey;
@@ -10,8 +11,8 @@ use ledger_device_sdk::nvm::*;
 use ledger_device_sdk::NVMData;
 
 // This is necessary to store the object in NVM and not in RAM
-// The max data saved here is for 4 participants, which sends 1024 bytes.
-pub const DKG_KEYS_MAX_SIZE: usize = 1300;
+// The max data saved here is for 7 participants, which takes around 1650 bytes.
+pub const DKG_KEYS_MAX_SIZE: usize = 2048;
 
 // Fix positions with u8 values
 const DKG_STATUS: usize = 0;
@@ -27,71 +28,148 @@ const IDENTITIES_POS: usize = 10;
 const KEY_PACKAGE_POS: usize = 12;
 const GROUP_KEY_PACKAGE_POS: usize = 14;
 const FROST_PUBLIC_PACKAGE_POS: usize = 16;
+// Saved on round 1, right after the identities. Zero if the round 1 data was saved by an older version
+const SESSION_DIGEST_POS: usize = 18;
 
 // Again, leave some bytes free for future entities (u16) we want to save something new... positions from 10 to 24 (7 entities, as 2 bytes per each)
 const DATA_STARTING_POS: u16 = 24;
 
+#[derive(Clone, Copy)]
 pub enum DkgKeyStatus {
-    Idle,
-    Initiated,
-    Completed,
+    Idle = 0,
+    Initiated = 1,
+    Completed = 2,
 }
 
 pub enum DkgKeyVersion {
     V1 = 1,
 }
 
+/// The DKG ceremony in progress, as saved on round 1
+pub struct DkgSession {
+    pub digest: [u8; DKG_SESSION_DIGEST_LEN],
+    pub min_signers: u8,
+}
+
+// Each account slot holds the keys of an independent multisig account
+pub const DKG_KEYS_SLOTS: usize = 4;
+
+// Slots are split in pages, so each write only updates the pages it touches,
+// instead of copying the whole slot to the stack.
+const PAGE_SIZE: usize = 256;
+const PAGES_PER_SLOT: usize = DKG_KEYS_MAX_SIZE / PAGE_SIZE;
+
+// Pages are aligned to 64 bytes and their size is a multiple of it, so they are laid out
+// contiguously and a slot can be read as a single slice
+const _: () = assert!(
+    core::mem::size_of::<[AlignedStorage<[u8; PAGE_SIZE]>; PAGES_PER_SLOT]>() == DKG_KEYS_MAX_SIZE
+);
+
+const EMPTY_PAGE: AlignedStorage<[u8; PAGE_SIZE]> = AlignedStorage::new([0u8; PAGE_SIZE]);
+const EMPTY_SLOT: [AlignedStorage<[u8; PAGE_SIZE]>; PAGES_PER_SLOT] = [EMPTY_PAGE; PAGES_PER_SLOT];
+
 #[link_section = ".nvm_data"]
-static mut DATA: NVMData<SafeStorage<[u8; DKG_KEYS_MAX_SIZE]>> =
-    NVMData::new(SafeStorage::new([0u8; DKG_KEYS_MAX_SIZE]));
+static mut DATA: NVMData<[[AlignedStorage<[u8; PAGE_SIZE]>; PAGES_PER_SLOT]; DKG_KEYS_SLOTS]> =
+    NVMData::new([EMPTY_SLOT; DKG_KEYS_SLOTS]);
+
+/// Summary of an account slot, as returned by the accounts listing and the DKG status
+pub struct DkgSlotSummary {
+    pub status: DkgKeyStatus,
+    pub identity_index: u8,
+    pub min_signers: u8,
+    pub participants: u8,
+}
 
 #[derive(Clone, Copy)]
-pub struct DkgKeys;
-
-impl Default for DkgKeys {
-    fn default() -> Self {
-        DkgKeys
-    }
+pub struct DkgKeys {
+    slot: usize,
 }
 
 impl DkgKeys {
-    pub fn is_valid_write(&self) -> Result<(), AppSW> {
-        let buffer = unsafe { DATA.get_mut() };
-        if !buffer.is_valid() {
-            return Err(AppSW::InvalidNVMWrite);
+    /// Keys stored on the given account slot
+    pub fn new(slot: u8) -> Result<Self, AppSW> {
+        let slot = slot as usize;
+        if slot >= DKG_KEYS_SLOTS {
+            return Err(AppSW::InvalidAccountSlot);
+        }
+
+        Ok(DkgKeys { slot })
+    }
+
+    /// Writes value at index, updating only the pages it spans
+    #[inline(never)]
+    fn write(&self, index: usize, value: &[u8]) -> Result<(), AppSW> {
+        self.write_with(index, value.len(), |dst, offset| {
+            dst.copy_from_slice(&value[offset..offset + dst.len()])
+        })
+    }
+
+    /// Zeroes out everything from index to the end of the slot
+    #[inline(never)]
+    fn clear(&self, index: usize) -> Result<(), AppSW> {
+        self.write_with(index, DKG_KEYS_MAX_SIZE - index, |dst, _| dst.fill(0))
+    }
+
+    /// Updates len bytes from index, one page at a time. fill gets the part of the page to
+    /// update, along with its offset from index. Each page is read back once written.
+    #[inline(never)]
+    fn write_with(
+        &self,
+        index: usize,
+        len: usize,
+        fill: impl Fn(&mut [u8], usize),
+    ) -> Result<(), AppSW> {
+        if index + len > DKG_KEYS_MAX_SIZE {
+            return Err(AppSW::BufferOutOfBounds);
+        }
+
+        let pages = unsafe { &mut DATA.get_mut()[self.slot] };
+
+        let mut done = 0;
+        while done < len {
+            let pos = index + done;
+            let page = &mut pages[pos / PAGE_SIZE];
+            let offset = pos % PAGE_SIZE;
+            let page_len = (len - done).min(PAGE_SIZE - offset);
+
+            let mut updated_page: [u8; PAGE_SIZE] = *page.get_ref();
+            fill(&mut updated_page[offset..offset + page_len], done);
+            page.update(&updated_page);
+
+            if page.get_ref()[offset..offset + page_len] != updated_page[offset..offset + page_len]
+            {
+                return Err(AppSW::InvalidNVMWrite);
+            }
+
+            done += page_len;
         }
 
         Ok(())
     }
 
+    /// Raw keys data of this slot
+    pub fn data(&self) -> &[u8] {
+        let pages = unsafe { &DATA.get_mut()[self.slot] };
+        unsafe { core::slice::from_raw_parts(pages.as_ptr() as *const u8, DKG_KEYS_MAX_SIZE) }
+    }
+
     #[inline(never)]
     pub fn get_element(&self, index: usize) -> Option<u8> {
-        let buffer_ref: [u8; DKG_KEYS_MAX_SIZE] = unsafe { *DATA.get_mut().get_ref() };
+        let buffer_ref = self.data();
         buffer_ref.get(index).copied()
     }
 
     #[inline(never)]
     pub fn get_u16(&self, start_pos: usize) -> Option<usize> {
-        let buffer_ref: [u8; DKG_KEYS_MAX_SIZE] = unsafe { *DATA.get_mut().get_ref() };
-        let bytes = buffer_ref[start_pos..start_pos + 2].try_into().ok()?;
+        let buffer_ref = self.data();
+        let bytes = buffer_ref.get(start_pos..start_pos + 2)?.try_into().ok()?;
         Some(u16::from_be_bytes(bytes) as usize)
     }
 
     #[inline(never)]
     pub fn set_element(&self, index: usize, value: u8) -> Result<(), AppSW> {
-        let mut updated_data: [u8; DKG_KEYS_MAX_SIZE] = unsafe { *DATA.get_mut().get_ref() };
-
-        updated_data
-            .get_mut(index)
-            .map(|v| *v = value)
-            .ok_or(AppSW::BufferOutOfBounds)?;
-
-        unsafe {
-            DATA.get_mut().update(&updated_data);
-        }
-
-        self.is_valid_write()?;
-        Ok(())
+        self.check_write_pos(index)?;
+        self.write(index, &[value])
     }
 
     #[inline(never)]
@@ -99,15 +177,7 @@ impl DkgKeys {
         let end_index = index + value.len();
         self.check_write_pos(end_index - 1)?; // Check only the last position
 
-        let mut updated_data: [u8; DKG_KEYS_MAX_SIZE] = unsafe { *DATA.get_mut().get_ref() };
-
-        updated_data[index..end_index].copy_from_slice(value);
-
-        unsafe {
-            DATA.get_mut().update(&updated_data);
-        }
-        self.is_valid_write()?;
-        Ok(())
+        self.write(index, value)
     }
 
     #[inline(never)]
@@ -117,18 +187,9 @@ impl DkgKeys {
         let total_len = 2 + len; // 2 bytes for length + actual data length
         self.check_write_pos(index + total_len - 1)?; // -1 to offset correctly the length and pos
 
-        let mut updated_data: [u8; DKG_KEYS_MAX_SIZE] = unsafe { *DATA.get_mut().get_ref() };
-
-        // Write length as big-endian u16
-        updated_data[index..index + 2].copy_from_slice(&(len as u16).to_be_bytes());
-
-        // Write the actual data
-        updated_data[index + 2..index + total_len].copy_from_slice(value);
-
-        unsafe {
-            DATA.get_mut().update(&updated_data);
-        }
-        self.is_valid_write()?;
+        // Write length as big-endian u16, followed by the actual data
+        self.write(index, &(len as u16).to_be_bytes())?;
+        self.write(index + 2, value)?;
 
         Ok(index + total_len)
     }
@@ -137,19 +198,8 @@ impl DkgKeys {
     pub fn set_u16(&self, index: usize, value: u16) -> Result<usize, AppSW> {
         self.check_write_pos(index + 1)?; // Check only the last position
 
-        let mut updated_data: [u8; DKG_KEYS_MAX_SIZE] = unsafe { *DATA.get_mut().get_ref() };
-
         // Convert u16 to big-endian bytes and copy them
-        updated_data
-            .get_mut(index..index + 2)
-            .map(|slice| slice.copy_from_slice(&value.to_be_bytes()))
-            .ok_or(AppSW::BufferOutOfBounds)?;
-
-        unsafe {
-            DATA.get_mut().update(&updated_data);
-        }
-
-        self.is_valid_write()?;
+        self.write(index, &value.to_be_bytes())?;
         Ok(index + 2)
     }
 
@@ -167,9 +217,14 @@ impl DkgKeys {
         identities: &Vec<Identity>,
         min_signers: u8,
         identity_index: u8,
+        session_digest: &[u8; DKG_SESSION_DIGEST_LEN],
     ) -> Result<(), AppSW> {
         zlog_stack("start save_round_1_data\0");
 
+        // A completed account may be overwritten. The slot reads as idle until round 1 data is
+        // fully written, so an interrupted write never leaves a completed account with new identities.
+        self.update_keys_status(DkgKeyStatus::Idle, DkgKeyVersion::V1)?;
+
         self.set_u16(IDENTITIES_POS, DATA_STARTING_POS)?;
 
         let mut pos = DATA_STARTING_POS as usize;
@@ -182,6 +237,9 @@ impl DkgKeys {
             pos += IDENTITY_LEN;
         }
 
+        self.set_u16(SESSION_DIGEST_POS, pos as u16)?;
+        self.set_slice_with_len(pos, session_digest)?;
+
         self.set_element(MIN_SIGNERS_POS, min_signers)?;
         self.set_element(IDENTITY_INDEX_POS, identity_index)?;
 
@@ -209,7 +267,7 @@ impl DkgKeys {
 
     #[inline(never)]
     pub fn save_keys(
-        &mut self,
+        &self,
         key_package: &KeyPackage,
         public_key_package: FrostPublicKeyPackage,
         group_secret_key: &GroupSecretKey,
@@ -224,15 +282,20 @@ impl DkgKeys {
             }
         }
 
-        // Read where the previous data end up
-        let identities_pos: usize = self
-            .get_u16(IDENTITIES_POS)
+        // Read where the previous data end up, the session digest is the last entity saved on round 1
+        let mut last_entity_pos: usize = self
+            .get_u16(SESSION_DIGEST_POS)
             .ok_or(AppSW::InvalidDkgStatus)?;
+        if last_entity_pos == 0 {
+            last_entity_pos = self
+                .get_u16(IDENTITIES_POS)
+                .ok_or(AppSW::InvalidDkgStatus)?;
+        }
 
-        let identities_len: usize = self
-            .get_u16(identities_pos)
+        let last_entity_len: usize = self
+            .get_u16(last_entity_pos)
             .ok_or(AppSW::InvalidDkgStatus)?;
-        let mut pos = identities_pos + 2 + identities_len;
+        let mut pos = last_entity_pos + 2 + last_entity_len;
 
         self.set_u16(KEY_PACKAGE_POS, pos as u16)?;
         pos = self.set_slice_with_len(pos, key_package.serialize().unwrap().as_slice())?;
@@ -241,6 +304,7 @@ impl DkgKeys {
         self.set_u16(FROST_PUBLIC_PACKAGE_POS, pos as u16)?;
         self.set_slice_with_len(pos, public_key_package.serialize().unwrap().as_slice())?;
 
+        // Only once every entity is written, so an interrupted write leaves the DKG in progress
         self.update_keys_status(DkgKeyStatus::Completed, DkgKeyVersion::V1)
     }
 
@@ -248,67 +312,113 @@ impl DkgKeys {
     pub fn restore_keys(&self, data: &[u8]) -> Result<(), AppSW> {
         zlog_stack("start restore_keys\0");
 
-        if data[DKG_VERSION] != 1 {
-            return Err(AppSW::InvalidDkgKeysVersion);
+        DkgKeysReader::validate_keys(data)?;
+
+        // Pages are written one at a time, so the status byte goes last. Until then the slot
+        // reads as idle, and an interrupted restore never looks like a completed account.
+        self.write(DKG_STATUS, &[0])?;
+        self.write(DKG_STATUS + 1, &data[DKG_STATUS + 1..])?;
+
+        // Whatever the slot held before must not be left behind the restored keys
+        self.clear(data.len())?;
+
+        self.write(DKG_STATUS, &data[..DKG_STATUS + 1])
+    }
+
+    /// Drops the DKG initiated on round 1, so a new one can be started
+    #[inline(never)]
+    pub fn abort_dkg(&self) -> Result<(), AppSW> {
+        zlog_stack("start abort_dkg\0");
+
+        match self.get_keys_status()? {
+            DkgKeyStatus::Initiated => self.delete_keys(),
+            _ => Err(AppSW::InvalidDkgStatus),
+        }
+    }
+
+    /// Zeroes out the whole slot. The status of the slot goes back to idle.
+    #[inline(never)]
+    pub fn delete_keys(&self) -> Result<(), AppSW> {
+        zlog_stack("start delete_keys\0");
+
+        self.clear(0)?;
+
+        // Make sure nothing was left behind
+        if self.data().iter().any(|b| *b != 0) {
+            return Err(AppSW::InvalidNVMWrite);
         }
 
-        self.set_slice(0, data)
+        Ok(())
+    }
+
+    #[inline(never)]
+    pub fn get_keys_status(&self) -> Result<DkgKeyStatus, AppSW> {
+        let buffer_ref = self.data();
+
+        DkgKeysReader::get_keys_status(buffer_ref)
     }
 
     #[inline(never)]
-    pub fn get_keys_status(&mut self) -> Result<DkgKeyStatus, AppSW> {
-        let buffer_ref: [u8; DKG_KEYS_MAX_SIZE] = unsafe { *DATA.get_mut().get_ref() };
+    pub fn summary(&self) -> Result<DkgSlotSummary, AppSW> {
+        let buffer_ref = self.data();
 
-        DkgKeysReader::get_keys_status(&buffer_ref)
+        DkgKeysReader::summary(buffer_ref)
     }
 
     #[inline(never)]
     pub fn backup_keys(&self) -> Result<Vec<u8>, AppSW> {
-        let buffer_ref: [u8; DKG_KEYS_MAX_SIZE] = unsafe { *DATA.get_mut().get_ref() };
+        let buffer_ref = self.data();
+
+        DkgKeysReader::backup_keys(buffer_ref)
+    }
+
+    #[inline(never)]
+    pub fn load_group_secret_key(&self) -> Result<GroupSecretKeyGuard, AppSW> {
+        let buffer_ref = self.data();
 
-        DkgKeysReader::backup_keys(&buffer_ref)
+        DkgKeysReader::load_group_secret_key(buffer_ref)
     }
 
     #[inline(never)]
-    pub fn load_group_secret_key(&mut self) -> Result<GroupSecretKeyGuard, AppSW> {
-        let buffer_ref: [u8; DKG_KEYS_MAX_SIZE] = unsafe { *DATA.get_mut().get_ref() };
+    pub fn load_frost_public_key_package(&self) -> Result<FrostPublicKeyPackage, AppSW> {
+        let buffer_ref = self.data();
 
-        DkgKeysReader::load_group_secret_key(&buffer_ref)
+        DkgKeysReader::load_frost_public_key_package(buffer_ref)
     }
 
     #[inline(never)]
-    pub fn load_frost_public_key_package(&mut self) -> Result<FrostPublicKeyPackage, AppSW> {
-        let buffer_ref: [u8; DKG_KEYS_MAX_SIZE] = unsafe { *DATA.get_mut().get_ref() };
+    pub fn load_key_package(&self) -> Result<KeyPackageGuard, AppSW> {
+        let buffer_ref = self.data();
 
-        DkgKeysReader::load_frost_public_key_package(&buffer_ref)
+        DkgKeysReader::load_key_package(buffer_ref)
     }
 
     #[inline(never)]
-    pub fn load_key_package(&mut self) -> Result<KeyPackageGuard, AppSW> {
-        let buffer_ref: [u8; DKG_KEYS_MAX_SIZE] = unsafe { *DATA.get_mut().get_ref() };
+    pub fn load_min_signers(&self) -> Result<usize, AppSW> {
+        let buffer_ref = self.data();
 
-        DkgKeysReader::load_key_package(&buffer_ref)
+        DkgKeysReader::load_min_signers(buffer_ref)
     }
 
     #[inline(never)]
-    pub fn load_min_signers(&mut self) -> Result<usize, AppSW> {
-        let buffer_ref: [u8; DKG_KEYS_MAX_SIZE] = unsafe { *DATA.get_mut().get_ref() };
+    pub fn load_identity_index(&self) -> Result<usize, AppSW> {
+        let buffer_ref = self.data();
 
-        DkgKeysReader::load_min_signers(&buffer_ref)
+        DkgKeysReader::load_identity_index(buffer_ref)
     }
 
     #[inline(never)]
-    pub fn load_identity_index(&mut self) -> Result<usize, AppSW> {
-        let buffer_ref: [u8; DKG_KEYS_MAX_SIZE] = unsafe { *DATA.get_mut().get_ref() };
+    pub fn load_identities(&self) -> Result<Vec<Identity>, AppSW> {
+        let buffer_ref = self.data();
 
-        DkgKeysReader::load_identity_index(&buffer_ref)
+        DkgKeysReader::load_identities(buffer_ref)
     }
 
     #[inline(never)]
-    pub fn load_identities(&mut self) -> Result<Vec<Identity>, AppSW> {
-        let buffer_ref: [u8; DKG_KEYS_MAX_SIZE] = unsafe { *DATA.get_mut().get_ref() };
+    pub fn load_dkg_session(&self) -> Result<DkgSession, AppSW> {
+        let buffer_ref = self.data();
 
-        DkgKeysReader::load_identities(&buffer_ref)
+        DkgKeysReader::load_dkg_session(buffer_ref)
     }
 }
 
@@ -478,6 +588,184 @@ impl DkgKeysReader {
         Ok(identities)
     }
 
+    #[inline(never)]
+    pub fn load_dkg_session(data: &[u8]) -> Result<DkgSession, AppSW> {
+        zlog_stack("start load_dkg_session\0");
+
+        let status = DkgKeysReader::get_keys_status(data)?;
+        match status {
+            DkgKeyStatus::Initiated => {}
+            _ => {
+                return Err(AppSW::InvalidDkgStatus);
+            }
+        }
+
+        let mut start = DkgKeysReader::get_u16(data, SESSION_DIGEST_POS);
+        if start == 0 {
+            return Err(AppSW::InvalidDkgSession);
+        }
+
+        let len = DkgKeysReader::get_u16(data, start);
+        start += 2;
+
+        let digest = DkgKeysReader::get_slice(data, start, start + len)
+            .try_into()
+            .map_err(|_| AppSW::InvalidDkgSession)?;
+        let min_signers = DkgKeysReader::get_element(data, MIN_SIGNERS_POS);
+
+        Ok(DkgSession {
+            digest,
+            min_signers,
+        })
+    }
+
+    /// Status of the keys, along with our identity index, the threshold and the participants count.
+    /// Those are saved on round 1, so they are known as soon as a DKG is initiated.
+    #[inline(never)]
+    pub fn summary(data: &[u8]) -> Result<DkgSlotSummary, AppSW> {
+        zlog_stack("start summary\0");
+
+        // Slots are read as they are, so positions must be checked before using them
+        if data.len() < DATA_STARTING_POS as usize {
+            return Err(AppSW::InvalidPayload);
+        }
+
+        let status = DkgKeysReader::get_keys_status(data)?;
+        let (identity_index, min_signers, participants) = match status {
+            DkgKeyStatus::Idle => (0, 0, 0),
+            DkgKeyStatus::Initiated | DkgKeyStatus::Completed => {
+                let start = DkgKeysReader::get_u16(data, IDENTITIES_POS);
+                if start + 2 > data.len() {
+                    return Err(AppSW::InvalidPayload);
+                }
+
+                let len = DkgKeysReader::get_u16(data, start);
+                if start + 2 + len > data.len() || len % IDENTITY_LEN != 0 {
+                    return Err(AppSW::InvalidPayload);
+                }
+
+                (
+                    DkgKeysReader::get_element(data, IDENTITY_INDEX_POS),
+                    DkgKeysReader::get_element(data, MIN_SIGNERS_POS),
+                    (len / IDENTITY_LEN) as u8,
+                )
+            }
+        };
+
+        Ok(DkgSlotSummary {
+            status,
+            identity_index,
+            min_signers,
+            participants,
+        })
+    }
+
+    /// Checks keys data coming from outside the device, as a backup, before storing it. Every
+    /// entity must lie right after the previous one, in the order they are saved, and the
+    /// entities must parse and agree with each other.
+    #[inline(never)]
+    pub fn validate_keys(data: &[u8]) -> Result<(), AppSW> {
+        zlog_stack("start validate_keys\0");
+
+        if data.len() < DATA_STARTING_POS as usize || data.len() > DKG_KEYS_MAX_SIZE {
+            return Err(AppSW::InvalidBackup);
+        }
+
+        if DkgKeysReader::get_element(data, DKG_VERSION) != DkgKeyVersion::V1 as u8 {
+            return Err(AppSW::InvalidDkgKeysVersion);
+        }
+
+        match DkgKeysReader::get_keys_status(data)? {
+            DkgKeyStatus::Completed => {}
+            _ => {
+                return Err(AppSW::InvalidDkgStatus);
+            }
+        }
+
+        let mut expected_start = DATA_STARTING_POS as usize;
+        for entity_pos in [
+            IDENTITIES_POS,
+            SESSION_DIGEST_POS,
+            KEY_PACKAGE_POS,
+            GROUP_KEY_PACKAGE_POS,
+            FROST_PUBLIC_PACKAGE_POS,
+        ] {
+            let start = DkgKeysReader::get_u16(data, entity_pos);
+
+            // Keys saved by older versions have no session digest
+            if entity_pos == SESSION_DIGEST_POS && start == 0 {
+                continue;
+            }
+
+            if start != expected_start || start + 2 > data.len() {
+                return Err(AppSW::InvalidBackup);
+            }
+
+            let len = DkgKeysReader::get_u16(data, start);
+            if (entity_pos == IDENTITIES_POS && len % IDENTITY_LEN != 0)
+                || (entity_pos == SESSION_DIGEST_POS && len != DKG_SESSION_DIGEST_LEN)
+            {
+                return Err(AppSW::InvalidBackup);
+            }
+
+            expected_start = start + 2 + len;
+            if expected_start > data.len() {
+                return Err(AppSW::InvalidBackup);
+            }
+        }
+
+        if expected_start != data.len() {
+            return Err(AppSW::InvalidBackup);
+        }
+
+        let identities = DkgKeysReader::load_identities(data)?;
+        let serialized_identities: Vec<_> = identities
+            .iter()
+            .map(|identity| identity.serialize())
+            .collect();
+        for (i, identity) in serialized_identities.iter().enumerate() {
+            if serialized_identities[..i].contains(identity) {
+                return Err(AppSW::DuplicatedIdentity);
+            }
+        }
+
+        let min_signers = DkgKeysReader::load_min_signers(data)?;
+        if min_signers < 2 || min_signers > identities.len() {
+            return Err(AppSW::InvalidMinSigners);
+        }
+
+        if DkgKeysReader::load_identity_index(data)? > MAX_IDENTITY_INDEX as usize {
+            return Err(AppSW::InvalidIdentityIndex);
+        }
+
+        drop(DkgKeysReader::load_group_secret_key(data)?);
+
+        let key_package = DkgKeysReader::load_key_package(data)?;
+        if *key_package.min_signers() as usize != min_signers
+            || !identities
+                .iter()
+                .any(|identity| identity.to_frost_identifier() == *key_package.identifier())
+        {
+            return Err(AppSW::InvalidKeyPackage);
+        }
+
+        // The public package must hold a verifying share for each participant,
+        // and ours must match the one in the key package
+        let public_key_package = DkgKeysReader::load_frost_public_key_package(data)?;
+        let verifying_shares = public_key_package.verifying_shares();
+        if verifying_shares.len() != identities.len()
+            || !identities
+                .iter()
+                .all(|identity| verifying_shares.contains_key(&identity.to_frost_identifier()))
+            || verifying_shares.get(key_package.identifier()) != Some(key_package.verifying_share())
+            || public_key_package.verifying_key() != key_package.verifying_key()
+        {
+            return Err(AppSW::InvalidPublicPackage);
+        }
+
+        Ok(())
+    }
+
     #[inline(never)]
     pub fn backup_keys(data: &[u8]) -> Result<Vec<u8>, AppSW> {
         zlog_stack("start backup_keys\0");
@@ -0,0 +1,102 @@
+use crate::bolos::zlog_stack;
+use crate::ironfish::constants::TX_HASH_LEN;
+use crate::AppSW;
+use blake2b_simd::Params as Blake2b;
+use ledger_device_sdk::nvm::*;
+use ledger_device_sdk::NVMData;
+
+// Signing nonces are derived deterministically from the tx hash, so signing the same hash
+// twice with a different signing package would reuse a nonce under a different challenge.
+// We remember which signing package was signed for the last few tx hashes to prevent it.
+// As nonces also depend on the key package, entries are kept per account slot.
+const SIGN_HISTORY_ENTRIES: usize = 16;
+
+pub const SIGNING_PACKAGE_DIGEST_LEN: usize = 32;
+const SIGNING_PACKAGE_PERSONALIZATION: &[u8; 16] = b"IronFishDkgSgPkg";
+
+const ENTRY_LEN: usize = 1 + TX_HASH_LEN + SIGNING_PACKAGE_DIGEST_LEN;
+
+// Position (u8) of the entry the next new tx hash will be written to
+const NEXT_ENTRY_POS: usize = 0;
+const ENTRIES_STARTING_POS: usize = 1;
+
+const SIGN_HISTORY_SIZE: usize = ENTRIES_STARTING_POS + SIGN_HISTORY_ENTRIES * ENTRY_LEN;
+
+#[link_section = ".nvm_data"]
+static mut DATA: NVMData<SafeStorage<[u8; SIGN_HISTORY_SIZE]>> =
+    NVMData::new(SafeStorage::new([0u8; SIGN_HISTORY_SIZE]));
+
+#[derive(Clone, Copy)]
+pub struct SignHistory;
+
+impl Default for SignHistory {
+    fn default() -> Self {
+        SignHistory
+    }
+}
+
+impl SignHistory {
+    /// Digest identifying a serialized signing package
+    #[inline(never)]
+    pub fn signing_package_digest(signing_package: &[u8]) -> [u8; SIGNING_PACKAGE_DIGEST_LEN] {
+        Blake2b::new()
+            .hash_length(SIGNING_PACKAGE_DIGEST_LEN)
+            .personal(SIGNING_PACKAGE_PERSONALIZATION)
+            .hash(signing_package)
+            .as_bytes()
+            .try_into()
+            .expect("hash has incorrect length")
+    }
+
+    /// Records that the signing package with the given digest is about to be signed for tx_hash
+    /// with the keys of the given account slot.
+    /// Signing the same tx hash again is only allowed with the very same signing package,
+    /// as a different one would reuse our nonces. Once the ring buffer is full, the oldest entry is replaced.
+    #[inline(never)]
+    pub fn check_and_record(
+        &self,
+        slot: u8,
+        tx_hash: &[u8],
+        digest: &[u8; SIGNING_PACKAGE_DIGEST_LEN],
+    ) -> Result<(), AppSW> {
+        zlog_stack("start check_and_record\0");
+
+        if tx_hash.len() != TX_HASH_LEN {
+            return Err(AppSW::InvalidTxHash);
+        }
+
+        let mut updated_data: [u8; SIGN_HISTORY_SIZE] = unsafe { *DATA.get_mut().get_ref() };
+
+        let entries = &updated_data[ENTRIES_STARTING_POS..];
+        for entry in entries.chunks_exact(ENTRY_LEN) {
+            if entry[0] == slot && entry[1..1 + TX_HASH_LEN] == tx_hash[..] {
+                if entry[1 + TX_HASH_LEN..] != digest[..] {
+                    zlog_stack("signing package reuse\0");
+                    return Err(AppSW::NonceReuse);
+                }
+
+                return Ok(());
+            }
+        }
+
+        let next_entry = updated_data[NEXT_ENTRY_POS] as usize % SIGN_HISTORY_ENTRIES;
+        let entry_pos = ENTRIES_STARTING_POS + next_entry * ENTRY_LEN;
+
+        updated_data[entry_pos] = slot;
+        updated_data[entry_pos + 1..entry_pos + 1 + TX_HASH_LEN].copy_from_slice(tx_hash);
+        updated_data[entry_pos + 1 + TX_HASH_LEN..entry_pos + ENTRY_LEN].copy_from_slice(digest);
+        updated_data[NEXT_ENTRY_POS] = ((next_entry + 1) % SIGN_HISTORY_ENTRIES) as u8;
+
+        unsafe {
+            DATA.get_mut().update(&updated_data);
+        }
+
+        // Never sign if the record could not be persisted
+        let buffer = unsafe { DATA.get_mut() };
+        if !buffer.is_valid() {
+            return Err(AppSW::InvalidNVMWrite);
+        }
+
+        Ok(())
+    }
+}
@@ -1,3 +1,4 @@
+mod asset;
 mod asset_identifier;
 mod constants;
 mod error;
@@ -9,6 +10,7 @@ mod object_list;
 mod transaction;
 mod tx_version;
 
+pub use asset::Asset;
 pub use asset_identifier::AssetIdentifier;
 pub use constants::*;
 pub use error::ParserError;
@@ -0,0 +1,77 @@
+use alloc::string::String;
+use arrayref::array_ref;
+use blake2s_simd::Params as Blake2s;
+
+use crate::ironfish::constants::GH_FIRST_BLOCK;
+use crate::parser::constants::{
+    ASSET_ID_LENGTH, ASSET_ID_PERSONALIZATION, ASSET_LEN, ASSET_METADATA_LEN, ASSET_NAME_LEN,
+    PUBLIC_ADDRESS_SIZE,
+};
+use crate::parser::AssetIdentifier;
+
+const NAME_OFFSET: usize = PUBLIC_ADDRESS_SIZE;
+const METADATA_OFFSET: usize = NAME_OFFSET + ASSET_NAME_LEN;
+const NONCE_OFFSET: usize = METADATA_OFFSET + ASSET_METADATA_LEN;
+
+/// Describes all the fields necessary for creating and transacting with an
+/// asset on the Iron Fish network. This is a view over the serialized
+/// asset carried by mint descriptions:
+/// 32-bytes creator(address)
+/// 32-bytes name
+/// 96-bytes metadata
+/// 1-byte nonce
+#[cfg_attr(test, derive(Debug))]
+#[derive(Copy, PartialEq, Clone)]
+pub struct Asset<'a>(&'a [u8; ASSET_LEN]);
+
+impl<'a> Asset<'a> {
+    pub fn new(data: &'a [u8; ASSET_LEN]) -> Self {
+        Self(data)
+    }
+
+    /// The address of the account that created this asset
+    pub fn creator(&self) -> &'a [u8; PUBLIC_ADDRESS_SIZE] {
+        let data: &'a [u8; ASSET_LEN] = self.0;
+        array_ref![data, 0, PUBLIC_ADDRESS_SIZE]
+    }
+
+    pub fn name(&self) -> &'a [u8; ASSET_NAME_LEN] {
+        let data: &'a [u8; ASSET_LEN] = self.0;
+        array_ref![data, NAME_OFFSET, ASSET_NAME_LEN]
+    }
+
+    pub fn metadata(&self) -> &'a [u8; ASSET_METADATA_LEN] {
+        let data: &'a [u8; ASSET_LEN] = self.0;
+        array_ref![data, METADATA_OFFSET, ASSET_METADATA_LEN]
+    }
+
+    pub fn nonce(&self) -> u8 {
+        self.0[NONCE_OFFSET]
+    }
+
+    /// Derives the asset identifier the same way the Iron Fish node does,
+    /// BLAKE2s(GH_FIRST_BLOCK | creator | name | metadata | nonce)
+    #[inline(never)]
+    pub fn id(&self) -> AssetIdentifier {
+        let hash = Blake2s::new()
+            .hash_length(ASSET_ID_LENGTH)
+            .personal(ASSET_ID_PERSONALIZATION)
+            .to_state()
+            .update(GH_FIRST_BLOCK)
+            .update(&self.0[..])
+            .finalize();
+
+        AssetIdentifier::new(*array_ref![hash.as_bytes(), 0, ASSET_ID_LENGTH])
+    }
+}
+
+/// Renders a zero padded text field (asset name or metadata),
+/// falling back to its hex representation if it is not valid utf8
+pub(crate) fn padded_to_string(bytes: &[u8]) -> String {
+    let len = bytes.iter().rposition(|b| *b != 0).map_or(0, |i| i + 1);
+
+    match core::str::from_utf8(&bytes[..len]) {
+        Ok(s) => String::from(s),
+        Err(_) => hex::encode(&bytes[..len]),
+    }
+}
@@ -1,11 +1,13 @@
 use core::ptr::addr_of_mut;
 
 use arrayref::array_ref;
+use blake2s_simd::Params as Blake2s;
+use jubjub::AffinePoint;
 use nom::bytes::complete::take;
 
 use crate::FromBytes;
 
-use crate::parser::constants::ASSET_ID_LENGTH;
+use crate::parser::constants::{ASSET_ID_LENGTH, VALUE_COMMITMENT_GENERATOR_PERSONALIZATION};
 
 /// A convenience wrapper around an asset id byte-array, allowing us to push the
 /// error checking of the asset id validity to instantiation
@@ -15,9 +17,26 @@ use crate::parser::constants::ASSET_ID_LENGTH;
 pub struct AssetIdentifier([u8; ASSET_ID_LENGTH]);
 
 impl AssetIdentifier {
+    pub fn new(bytes: [u8; ASSET_ID_LENGTH]) -> Self {
+        Self(bytes)
+    }
+
     pub fn as_bytes(&self) -> &[u8; ASSET_ID_LENGTH] {
         &self.0
     }
+
+    /// The asset generator point derived from this identifier,
+    /// the cofactor is not cleared
+    pub fn asset_generator(&self) -> Option<AffinePoint> {
+        let hash = Blake2s::new()
+            .hash_length(32)
+            .personal(VALUE_COMMITMENT_GENERATOR_PERSONALIZATION)
+            .to_state()
+            .update(&self.0)
+            .finalize();
+
+        Option::from(AffinePoint::from_bytes(*hash.as_array()))
+    }
 }
 
 impl<'a> FromBytes<'a> for AssetIdentifier {
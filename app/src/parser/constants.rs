@@ -29,6 +29,8 @@ pub const BURN_LEN: usize = 32 + 8;
 // 96-bytes(metadata_len)
 // 1-byte(nonce)
 pub const ASSET_LEN: usize = 161; //193;
+pub const ASSET_NAME_LEN: usize = 32;
+pub const ASSET_METADATA_LEN: usize = 96;
 pub const REDJUBJUB_SIGNATURE_LEN: usize = 64;
 pub const KEY_LENGTH: usize = 32;
 pub const SCALAR_SIZE: usize = 32;
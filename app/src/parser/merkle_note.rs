@@ -127,8 +127,7 @@ impl<'a> MerkleNote<'a> {
         let note =
             Note::from_spender_encrypted(public_address.0, &shared_key, self.encrypted_note)?;
 
-        // FIXME: Verify the node commitment
-        // note.verify_commitment(self.note_commitment)?;
+        note.verify_commitment(self.note_commitment)?;
 
         Ok(note)
     }
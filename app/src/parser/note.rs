@@ -1,6 +1,6 @@
 use alloc::{string::String, vec::Vec};
 use core::{mem::MaybeUninit, ptr::addr_of_mut};
-use jubjub::AffinePoint;
+use jubjub::{AffinePoint, ExtendedPoint};
 use nom::number::complete::le_u64;
 
 #[cfg(feature = "ledger")]
@@ -8,14 +8,25 @@ use crate::nvm::settings::Settings;
 use crate::{
     bolos::zlog_stack,
     crypto::{decrypt, read_scalar},
-    ironfish::{errors::IronfishError, public_address::PublicAddress},
+    ironfish::{
+        constants::NOTE_COMMITMENT_RANDOMNESS_GENERATOR,
+        errors::IronfishError,
+        pedersen_hash::{pedersen_hash, Personalization},
+        public_address::PublicAddress,
+    },
     parser::AssetIdentifier,
     token::TokenList,
     utils::int_format::{token_to_fp_str, u64_to_str},
     FromBytes,
 };
 
-use super::{memo::Memo, ParserError, ENCRYPTED_NOTE_SIZE, MAC_SIZE};
+use super::{
+    memo::Memo, ParserError, AMOUNT_VALUE_SIZE, ASSET_ID_LENGTH, ENCRYPTED_NOTE_SIZE, MAC_SIZE,
+    PUBLIC_ADDRESS_SIZE,
+};
+
+// asset generator, value, owner and sender
+const NOTE_CONTENTS_SIZE: usize = ASSET_ID_LENGTH + AMOUNT_VALUE_SIZE + 2 * PUBLIC_ADDRESS_SIZE;
 
 /// A note (think bank note) represents a value in the owner's "account".
 /// When spending, proof that the note exists in the tree needs to be provided,
@@ -118,27 +129,61 @@ impl Note {
         Ok(())
     }
 
-    ///// Verify that the note's commitment matches the one passed in
-    //pub(crate) fn verify_commitment(&self, commitment: Scalar) -> Result<(), IronfishError> {
-    //    if commitment == self.commitment_point() {
-    //        Ok(())
-    //    } else {
-    //        Err(IronfishError::InvalidCommitment)
-    //    }
-    //}
-    //
-    ///// Compute the commitment of this note. This is essentially a hash of all
-    ///// the note values, including randomness.
-    /////
-    ///// The owner can publish this value to commit to the fact that the note
-    ///// exists, without revealing any of the values on the note until later.
-    //pub(crate) fn commitment_point(&self) -> Scalar {
-    //    // The commitment is in the prime order subgroup, so mapping the
-    //    // commitment to the u-coordinate is an injective encoding.
-    //    jubjub::ExtendedPoint::from(self.commitment_full_point())
-    //        .to_affine()
-    //        .get_u()
-    //}
+    /// Verify that the note's commitment matches the one passed in
+    pub(crate) fn verify_commitment(&self, commitment: &[u8; 32]) -> Result<(), IronfishError> {
+        if commitment == &self.commitment_point()? {
+            Ok(())
+        } else {
+            Err(IronfishError::InvalidCommitment)
+        }
+    }
+
+    /// Compute the commitment of this note. This is essentially a hash of all
+    /// the note values, including randomness.
+    ///
+    /// The owner can publish this value to commit to the fact that the note
+    /// exists, without revealing any of the values on the note until later.
+    pub(crate) fn commitment_point(&self) -> Result<[u8; 32], IronfishError> {
+        // The commitment is in the prime order subgroup, so mapping the
+        // commitment to the u-coordinate is an injective encoding.
+        let point = AffinePoint::from(self.commitment_full_point()?);
+        Ok(point.get_u().to_bytes())
+    }
+
+    /// Computes the note commitment, returning the full point.
+    #[inline(never)]
+    fn commitment_full_point(&self) -> Result<ExtendedPoint, IronfishError> {
+        let asset_generator = self
+            .asset_id
+            .asset_generator()
+            .ok_or(IronfishError::InvalidAssetIdentifier)?;
+
+        // Calculate the note contents, as bytes
+        let mut note_contents = [0u8; NOTE_CONTENTS_SIZE];
+        let contents = [
+            &asset_generator.to_bytes()[..],
+            &self.value.to_le_bytes()[..],
+            &self.owner.public_address()[..],
+            &self.sender.public_address()[..],
+        ];
+
+        let mut pos = 0;
+        for item in contents {
+            note_contents[pos..pos + item.len()].copy_from_slice(item);
+            pos += item.len();
+        }
+
+        // Compute the Pedersen hash of the note contents
+        let hash_of_contents = pedersen_hash(
+            Personalization::NoteCommitment,
+            note_contents
+                .iter()
+                .flat_map(|byte| (0..8).map(move |i| ((byte >> i) & 1) == 1)),
+        );
+
+        // Compute final commitment
+        Ok(NOTE_COMMITMENT_RANDOMNESS_GENERATOR * self.randomness + hash_of_contents)
+    }
 }
 
 impl Note {
@@ -1,18 +1,33 @@
+use alloc::{string::String, vec::Vec};
 use blake2b_simd::State;
 use core::mem::MaybeUninit;
 use core::ptr::addr_of_mut;
 
 use nom::bytes::complete::take;
+use nom::number::complete::le_u64;
 
 use crate::bolos::zlog_stack;
+#[cfg(feature = "ledger")]
+use crate::nvm::settings::Settings;
 use crate::parser::constants::BURN_LEN;
+use crate::parser::AssetIdentifier;
+use crate::token::TokenList;
+use crate::utils::int_format::{token_to_fp_str, u64_to_str};
 
 use super::FromBytes;
 use crate::parser::ParserError;
 
+/// A burn description, removing `value` units of
+/// the asset `asset_id` from circulation:
+/// 32-bytes asset_id
+/// 8-bytes value
 #[cfg_attr(test, derive(Debug))]
 #[derive(Copy, PartialEq, Clone)]
-pub struct Burn<'a>(&'a [u8]);
+pub struct Burn<'a> {
+    data: &'a [u8],
+    asset_id: AssetIdentifier,
+    value: u64,
+}
 
 impl<'a> FromBytes<'a> for Burn<'a> {
     #[inline(never)]
@@ -25,8 +40,13 @@ impl<'a> FromBytes<'a> for Burn<'a> {
 
         let (rem, data) = take(BURN_LEN)(input)?;
 
+        let asset_id = unsafe { &mut *addr_of_mut!((*out).asset_id).cast() };
+        let value_bytes = AssetIdentifier::from_bytes_into(data, asset_id)?;
+        let (_, value) = le_u64(value_bytes)?;
+
         unsafe {
-            addr_of_mut!((*out).0).write(data);
+            addr_of_mut!((*out).data).write(data);
+            addr_of_mut!((*out).value).write(value);
         }
 
         Ok(rem)
@@ -34,12 +54,107 @@ impl<'a> FromBytes<'a> for Burn<'a> {
 }
 
 impl<'a> Burn<'a> {
+    pub fn asset_id(&self) -> &AssetIdentifier {
+        &self.asset_id
+    }
+
+    pub fn value(&self) -> u64 {
+        self.value
+    }
+
     #[inline(never)]
     pub fn run_hash(&self, hasher: &mut State) {
         // both serialization and
         // hashing uses the same serialize_signature_fields
         // function so we can be sure inner data is correctly passed
         // to the hasher
-        hasher.update(self.0);
+        hasher.update(self.data);
+    }
+
+    /// Returns the fields to be displayed for this burn:
+    /// Burn Amount: the amount to be burnt
+    /// Burn AssetId: Only if token is unknown
+    #[inline(never)]
+    pub fn review_fields(
+        &self,
+        token_list: &TokenList,
+    ) -> Result<Vec<(String, String)>, ParserError> {
+        use lexical_core::FormattedSize;
+
+        zlog_stack("Burn::review_fields\n");
+
+        let mut fields = Vec::new();
+
+        let mut buffer = [0; u64::FORMATTED_SIZE_DECIMAL + 2];
+        let asset_id = hex::encode(self.asset_id.as_bytes());
+
+        if let Some(token) = token_list.token(&asset_id) {
+            let amount_formatted =
+                token_to_fp_str(self.value, &mut buffer[..], token.decimals as usize)?;
+            let mut amount_formatted = String::from(
+                core::str::from_utf8(amount_formatted).map_err(|_| ParserError::UnexpectedValue)?,
+            );
+            amount_formatted.push(' ');
+            amount_formatted.push_str(token.symbol);
+
+            fields.push((String::from("Burn Amount"), amount_formatted));
+        } else {
+            zlog_stack("Burn::unknown_token\n");
+
+            #[cfg(feature = "ledger")]
+            if !Settings.app_expert_mode() {
+                return Err(ParserError::ErrExpertModeMustBeEnabled);
+            }
+
+            let value_str = u64_to_str(self.value, &mut buffer)?;
+            let value_str =
+                core::str::from_utf8(value_str).map_err(|_| ParserError::UnexpectedValue)?;
+
+            fields.push((String::from("Burn Raw Amount"), String::from(value_str)));
+            fields.push((String::from("Burn AssetId"), asset_id));
+        }
+
+        Ok(fields)
+    }
+}
+
+#[cfg(test)]
+mod burn_test {
+    use super::*;
+    use crate::token::get_token_list;
+
+    const IRON_BURN: &str =
+        "51f33a2f14f92735e562dc658a5639279ddca3d5079a6d1242b2a588a9cbf44c00e1f50500000000";
+    const CUSTOM_BURN: &str =
+        "da40fc530a12327a1c1c367b8bd66ada35100501228cdb355b8fb6c40d048a640500000000000000";
+
+    #[test]
+    fn parse_burn() {
+        let bytes = hex::decode(CUSTOM_BURN).unwrap();
+        let (rem, burn) = Burn::from_bytes(&bytes).unwrap();
+        assert!(rem.is_empty());
+        assert_eq!(hex::encode(burn.asset_id().as_bytes()), &CUSTOM_BURN[..64]);
+        assert_eq!(burn.value(), 5);
+    }
+
+    #[test]
+    fn burn_review_fields() {
+        let token_list = get_token_list().unwrap();
+
+        let bytes = hex::decode(IRON_BURN).unwrap();
+        let (_, burn) = Burn::from_bytes(&bytes).unwrap();
+        let fields = burn.review_fields(&token_list).unwrap();
+        assert_eq!(fields, [("Burn Amount".into(), "1 IRON".into())]);
+
+        let bytes = hex::decode(CUSTOM_BURN).unwrap();
+        let (_, burn) = Burn::from_bytes(&bytes).unwrap();
+        let fields = burn.review_fields(&token_list).unwrap();
+        assert_eq!(
+            fields,
+            [
+                ("Burn Raw Amount".into(), "5".into()),
+                ("Burn AssetId".into(), CUSTOM_BURN[..64].into()),
+            ]
+        );
     }
 }
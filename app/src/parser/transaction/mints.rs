@@ -1,14 +1,29 @@
+use alloc::{string::String, vec::Vec};
+use arrayref::array_ref;
 use blake2b_simd::State;
 use core::mem::MaybeUninit;
 use core::ptr::addr_of_mut;
 
 use nom::bytes::complete::take;
 
-use crate::parser::constants::MINT_LEN;
+use crate::bolos::zlog_stack;
+#[cfg(feature = "ledger")]
+use crate::nvm::settings::Settings;
+use crate::parser::asset::padded_to_string;
+use crate::parser::constants::{ASSET_LEN, MINT_LEN, PUBLIC_ADDRESS_SIZE};
+use crate::parser::Asset;
+use crate::token::TokenList;
+use crate::utils::int_format::{token_to_fp_str, u64_to_str};
 use crate::TransactionVersion;
 
 use crate::parser::ParserError;
 
+// 32-bytes public_key randomness + 192-bytes proof
+const ASSET_OFFSET: usize = 32 + 192;
+const VALUE_OFFSET: usize = ASSET_OFFSET + ASSET_LEN;
+// owner(32) and flag(1) are only present in V2 transactions
+const TRANSFER_OWNERSHIP_OFFSET: usize = MINT_LEN + PUBLIC_ADDRESS_SIZE + 1;
+
 #[cfg_attr(test, derive(Debug))]
 #[derive(Copy, PartialEq, Clone)]
 pub struct MintList<'a> {
@@ -149,6 +164,87 @@ impl<'a> Mint<'a> {
         let to_hash = &self.data[32..self.data.len() - 64];
         hasher.update(to_hash);
     }
+
+    pub fn asset(&self) -> Asset<'a> {
+        let data: &'a [u8] = self.data;
+        Asset::new(array_ref![data, ASSET_OFFSET, ASSET_LEN])
+    }
+
+    pub fn value(&self) -> u64 {
+        u64::from_le_bytes(*array_ref![self.data, VALUE_OFFSET, 8])
+    }
+
+    /// The new owner of the asset, only V2 mints can transfer
+    /// the asset ownership
+    pub fn transfer_ownership_to(&self) -> Option<&'a [u8; PUBLIC_ADDRESS_SIZE]> {
+        let data: &'a [u8] = self.data;
+        self.has_transfer_ownership_to
+            .then(|| array_ref![data, TRANSFER_OWNERSHIP_OFFSET, PUBLIC_ADDRESS_SIZE])
+    }
+
+    /// Returns the fields to be displayed for this mint:
+    /// Mint Name and Mint Metadata: decoded from the asset
+    /// Mint Creator: the address that created the asset
+    /// Mint Amount: the amount to be minted
+    /// Mint AssetId: the derived asset identifier
+    /// Transfer To: Only if the mint transfers the asset ownership
+    #[inline(never)]
+    pub fn review_fields(
+        &self,
+        token_list: &TokenList,
+    ) -> Result<Vec<(String, String)>, ParserError> {
+        use lexical_core::FormattedSize;
+
+        zlog_stack("Mint::review_fields\n");
+
+        let mut fields = Vec::new();
+        let asset = self.asset();
+
+        fields.push((String::from("Mint Name"), padded_to_string(asset.name())));
+
+        let metadata = padded_to_string(asset.metadata());
+        if !metadata.is_empty() {
+            fields.push((String::from("Mint Metadata"), metadata));
+        }
+
+        fields.push((String::from("Mint Creator"), hex::encode(asset.creator())));
+
+        let mut buffer = [0; u64::FORMATTED_SIZE_DECIMAL + 2];
+        let asset_id = hex::encode(asset.id().as_bytes());
+
+        if let Some(token) = token_list.token(&asset_id) {
+            let amount_formatted =
+                token_to_fp_str(self.value(), &mut buffer[..], token.decimals as usize)?;
+            let mut amount_formatted = String::from(
+                core::str::from_utf8(amount_formatted).map_err(|_| ParserError::UnexpectedValue)?,
+            );
+            amount_formatted.push(' ');
+            amount_formatted.push_str(token.symbol);
+
+            fields.push((String::from("Mint Amount"), amount_formatted));
+        } else {
+            zlog_stack("Mint::unknown_token\n");
+
+            #[cfg(feature = "ledger")]
+            if !Settings.app_expert_mode() {
+                return Err(ParserError::ErrExpertModeMustBeEnabled);
+            }
+
+            let value_str = u64_to_str(self.value(), &mut buffer)?;
+            let value_str =
+                core::str::from_utf8(value_str).map_err(|_| ParserError::UnexpectedValue)?;
+
+            fields.push((String::from("Mint Raw Amount"), String::from(value_str)));
+        }
+
+        fields.push((String::from("Mint AssetId"), asset_id));
+
+        if let Some(new_owner) = self.transfer_ownership_to() {
+            fields.push((String::from("Transfer To"), hex::encode(new_owner)));
+        }
+
+        Ok(fields)
+    }
 }
 
 impl<'a> Iterator for MintIterator<'a> {
@@ -171,3 +267,93 @@ impl<'a> Iterator for MintIterator<'a> {
         }
     }
 }
+
+#[cfg(test)]
+mod mint_test {
+    use super::*;
+    use crate::token::get_token_list;
+
+    // Testcoin, created by 40fae059...
+    const ASSET: &str = "40fae059d8ee3361b7a08429867254523f937c7654ae6fe7b188c1f0da57e9cd54657374636f696e00000000000000000000000000000000000000000000000041207265616c6c7920636f6f6c20636f696e00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000001";
+    const OWNER: &str = "40fae059d8ee3361b7a08429867254523f937c7654ae6fe7b188c1f0da57e9cd";
+    const NEW_OWNER: &str = "b26388e8e7c12c80c7f20a8310137d4eb6b4bf3674e8a702b26ff4955f3d58c0";
+
+    /// Randomness and proof are not checked by the parser, so they are left zeroed.
+    /// V2 mints carry the owner and an optional new owner after the value.
+    fn mint_bytes(version: TransactionVersion, transfer_to: Option<&str>) -> Vec<u8> {
+        let mut bytes = vec![0u8; 32 + 192];
+        bytes.extend(hex::decode(ASSET).unwrap());
+        bytes.extend(5u64.to_le_bytes());
+        if version.has_mint_transfer_ownership_to() {
+            bytes.extend(hex::decode(OWNER).unwrap());
+            match transfer_to {
+                Some(new_owner) => {
+                    bytes.push(1);
+                    bytes.extend(hex::decode(new_owner).unwrap());
+                }
+                None => bytes.push(0),
+            }
+        }
+        bytes.extend([0u8; 64]);
+        bytes
+    }
+
+    fn parse_mint(bytes: &[u8], version: TransactionVersion) -> Mint<'_> {
+        let mut mint = MaybeUninit::uninit();
+        let rem = Mint::parse_into(bytes, version, &mut mint).unwrap();
+        assert!(rem.is_empty());
+        unsafe { mint.assume_init() }
+    }
+
+    #[test]
+    fn mint_v1_review_fields() {
+        let token_list = get_token_list().unwrap();
+
+        let bytes = mint_bytes(TransactionVersion::V1, None);
+        let mint = parse_mint(&bytes, TransactionVersion::V1);
+        assert_eq!(mint.transfer_ownership_to(), None);
+
+        let fields = mint.review_fields(&token_list).unwrap();
+        let names: Vec<&str> = fields.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(
+            names,
+            [
+                "Mint Name",
+                "Mint Metadata",
+                "Mint Creator",
+                "Mint Raw Amount",
+                "Mint AssetId"
+            ]
+        );
+        assert_eq!(fields[0].1, "Testcoin");
+        assert_eq!(fields[1].1, "A really cool coin");
+        assert_eq!(fields[2].1, OWNER);
+        assert_eq!(fields[3].1, "5");
+    }
+
+    #[test]
+    fn mint_v2_transfer_ownership_review_fields() {
+        let token_list = get_token_list().unwrap();
+
+        let bytes = mint_bytes(TransactionVersion::V2, Some(NEW_OWNER));
+        let mint = parse_mint(&bytes, TransactionVersion::V2);
+        assert_eq!(
+            mint.transfer_ownership_to().map(hex::encode),
+            Some(NEW_OWNER.into())
+        );
+
+        let fields = mint.review_fields(&token_list).unwrap();
+        assert_eq!(
+            fields.last().unwrap(),
+            &("Transfer To".into(), NEW_OWNER.into())
+        );
+
+        // Without a new owner, nothing is transferred
+        let bytes = mint_bytes(TransactionVersion::V2, None);
+        let mint = parse_mint(&bytes, TransactionVersion::V2);
+        assert_eq!(mint.transfer_ownership_to(), None);
+
+        let fields = mint.review_fields(&token_list).unwrap();
+        assert!(fields.iter().all(|(name, _)| name != "Transfer To"));
+    }
+}
@@ -42,6 +42,21 @@ pub enum AppSW {
     InvalidTxHash = 0xB025,
     InvalidToken = 0xB026,
     ErrExpertModeMustBeEnabled = 0xB027,
+    InvalidNoteCommitment = 0xB028,
+    TxAccountMismatch = 0xB029,
+    InvalidSigningMessage = 0xB02A,
+    InvalidSigningCommitment = 0xB02B,
+    NonceReuse = 0xB02C,
+    InvalidSignatureShare = 0xB02D,
+    InvalidParticipantShare = 0xB02E,
+    UnknownSigner = 0xB02F,
+    OwnIdentityNotFound = 0xB030,
+    DuplicatedIdentity = 0xB031,
+    InvalidMinSigners = 0xB032,
+    InvalidDkgSession = 0xB033,
+    InvalidAccountSlot = 0xB034,
+    BackupAccountMismatch = 0xB035,
+    InvalidBackup = 0xB036,
     #[cfg(feature = "ledger")]
     WrongApduLength = StatusWords::BadLen as u16,
     Ok = 0x9000,
@@ -61,6 +76,8 @@ impl From<IronfishError> for AppSW {
             IronfishError::InvalidSigningKey => AppSW::KeyDeriveFail,
             IronfishError::InvalidSecret => AppSW::InvalidGroupSecretKey,
             IronfishError::ErrExpertModeMustBeEnabled => AppSW::ErrExpertModeMustBeEnabled,
+            IronfishError::InvalidCommitment => AppSW::InvalidNoteCommitment,
+            IronfishError::InvalidAuthorizingKey => AppSW::TxAccountMismatch,
             // For errors that don't have a direct mapping, use a generic error
             _ => AppSW::Deny,
         }
@@ -1,4 +1,5 @@
 mod bip32;
+mod fingerprint;
 #[cfg(feature = "ledger")]
 pub mod response;
 #[macro_use]
@@ -8,6 +9,7 @@ use crate::bolos::zlog_stack;
 use alloc::string::{String, ToString};
 pub use bip32::Bip32Path;
 use core::cmp;
+pub use fingerprint::fingerprint;
 
 pub fn str_to_array<const SIZE: usize>(string: &str) -> [u8; SIZE] {
     let bytes = string.as_bytes();
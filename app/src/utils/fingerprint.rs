@@ -0,0 +1,50 @@
+use alloc::string::String;
+use blake2b_simd::Params as Blake2b;
+
+const FINGERPRINT_PERSONALIZATION: &[u8; 16] = b"IronFishDkgFgrpt";
+const FINGERPRINT_LEN: usize = 8;
+
+/// Short digest over the given data, meant to be compared by humans.
+/// It is rendered as groups of 4 hex chars, i.e. "1a2b-3c4d-5e6f-7a8b"
+#[inline(never)]
+pub fn fingerprint(data: &[&[u8]]) -> String {
+    let mut state = Blake2b::new()
+        .hash_length(FINGERPRINT_LEN)
+        .personal(FINGERPRINT_PERSONALIZATION)
+        .to_state();
+
+    for chunk in data {
+        // Length prefixed, so different splits of the same bytes do not collide
+        state.update(&(chunk.len() as u32).to_le_bytes());
+        state.update(chunk);
+    }
+
+    let hash = hex::encode(state.finalize().as_bytes());
+
+    let mut out = String::with_capacity(hash.len() + hash.len() / 4);
+    for (i, c) in hash.chars().enumerate() {
+        if i > 0 && i % 4 == 0 {
+            out.push('-');
+        }
+        out.push(c);
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod fingerprint_test {
+    use super::*;
+
+    #[test]
+    fn fingerprint_format() {
+        let fp = fingerprint(&[b"identity", &[2]]);
+        assert_eq!(fp.len(), 19);
+        assert_eq!(fp.split('-').count(), 4);
+
+        // Same input, same fingerprint
+        assert_eq!(fp, fingerprint(&[b"identity", &[2]]));
+        // Chunks boundaries are part of the fingerprint
+        assert_ne!(fp, fingerprint(&[b"identity\x02"]));
+    }
+}
@@ -0,0 +1,153 @@
+import { defaultOptions, identities, models, restoreKeysTestCases } from './common'
+import Zemu, { ButtonKind, IDeviceModel, isTouchDevice } from '@zondax/zemu'
+import { sendCommand, startTextFn } from './utils'
+import IronfishApp, { IronfishKeys } from '@zondax/ledger-ironfish'
+
+jest.setTimeout(450000)
+
+// Not covered by the js lib yet
+const INS_DKG_DELETE_KEYS = 0x20
+const INS_DKG_GET_STATUS = 0x21
+const INS_DKG_ABORT = 0x22
+
+const STATUS_IDLE = 0
+const STATUS_INITIATED = 1
+const STATUS_COMPLETED = 2
+
+const SW_INVALID_DKG_STATUS = 0xb022
+
+const startOptions = (m: IDeviceModel) => ({
+  ...defaultOptions,
+  model: m.name,
+  startText: startTextFn(m.name),
+  approveKeyword: isTouchDevice(m.name) ? 'Approve' : '',
+  approveAction: ButtonKind.ApproveTapButton,
+})
+
+// Leaves the account from the restore test case on slot 0
+const restoreAccount = async (m: IDeviceModel, sim: Zemu, app: IronfishApp, name: string) => {
+  const respReq = app.dkgRestoreKeys(restoreKeysTestCases[0].encrypted[0])
+  await sim.waitUntilScreenIsNot(sim.getMainMenuSnapshot())
+  await sim.compareSnapshotsAndApprove('.', `${m.prefix.toLowerCase()}-${name}-restore`)
+  await respReq
+  await sim.deleteEvents()
+}
+
+const getStatus = async (sim: Zemu) => (await sendCommand(sim, INS_DKG_GET_STATUS, 0, 0))[0]
+
+describe.each(models)('accounts', function (m) {
+  test.concurrent(`${m.name} - round 1 replaces a completed account once confirmed`, async () => {
+    const sim = new Zemu(m.path)
+    try {
+      await sim.start(startOptions(m))
+      const app = new IronfishApp(sim.getTransport(), true)
+
+      await restoreAccount(m, sim, app, 'round1-replace')
+      expect(await getStatus(sim)).toBe(STATUS_COMPLETED)
+
+      const round1Req = app.dkgRound1(
+        0,
+        identities.slice(0, 3).map(({ v }) => v),
+        2,
+      )
+
+      await sim.waitUntilScreenIsNot(sim.getMainMenuSnapshot())
+      await sim.compareSnapshotsAndApprove('.', `${m.prefix.toLowerCase()}-round1-replace-account`)
+
+      // Round 1 is only reviewed once the replacement is accepted
+      await sim.waitForText('Round 1')
+      await sim.compareSnapshotsAndApprove('.', `${m.prefix.toLowerCase()}-round1-replace-round1`)
+
+      const round1 = await round1Req
+      expect(round1.publicPackage.length).toBeTruthy()
+      expect(await getStatus(sim)).toBe(STATUS_INITIATED)
+    } finally {
+      await sim.close()
+    }
+  })
+
+  test.concurrent(`${m.name} - round 1 keeps a completed account if the replacement is rejected`, async () => {
+    const sim = new Zemu(m.path)
+    try {
+      await sim.start(startOptions(m))
+      const app = new IronfishApp(sim.getTransport(), true)
+
+      await restoreAccount(m, sim, app, 'round1-keep')
+
+      const round1Req = app.dkgRound1(
+        0,
+        identities.slice(0, 3).map(({ v }) => v),
+        2,
+      )
+
+      await sim.waitUntilScreenIsNot(sim.getMainMenuSnapshot())
+      await sim.compareSnapshotsAndReject('.', `${m.prefix.toLowerCase()}-round1-keep-account`)
+
+      await expect(round1Req).rejects.toThrow()
+      expect(await getStatus(sim)).toBe(STATUS_COMPLETED)
+    } finally {
+      await sim.close()
+    }
+  })
+
+  test.concurrent(`${m.name} - delete an account`, async () => {
+    const sim = new Zemu(m.path)
+    try {
+      await sim.start(startOptions(m))
+      const app = new IronfishApp(sim.getTransport(), true)
+
+      await restoreAccount(m, sim, app, 'delete')
+      expect(await getStatus(sim)).toBe(STATUS_COMPLETED)
+
+      const deleteReq = sendCommand(sim, INS_DKG_DELETE_KEYS, 0, 0)
+      await sim.waitUntilScreenIsNot(sim.getMainMenuSnapshot())
+      await sim.compareSnapshotsAndApprove('.', `${m.prefix.toLowerCase()}-delete-account`)
+      await deleteReq
+
+      // Nothing is left to sign with
+      expect(await sendCommand(sim, INS_DKG_GET_STATUS, 0, 0)).toEqual(Buffer.from([STATUS_IDLE, 0, 0, 0]))
+      await expect(app.dkgRetrieveKeys(IronfishKeys.PublicAddress)).rejects.toThrow()
+    } finally {
+      await sim.close()
+    }
+  })
+
+  test.concurrent(`${m.name} - abort a DKG in progress`, async () => {
+    const sim = new Zemu(m.path)
+    try {
+      await sim.start(startOptions(m))
+      const app = new IronfishApp(sim.getTransport(), true)
+
+      const round1Req = app.dkgRound1(
+        0,
+        identities.slice(0, 3).map(({ v }) => v),
+        2,
+      )
+      await sim.waitUntilScreenIsNot(sim.getMainMenuSnapshot())
+      await sim.compareSnapshotsAndApprove('.', `${m.prefix.toLowerCase()}-abort-round1`)
+      await round1Req
+
+      expect(await sendCommand(sim, INS_DKG_GET_STATUS, 0, 0)).toEqual(Buffer.from([STATUS_INITIATED, 0, 2, 3]))
+
+      await sendCommand(sim, INS_DKG_ABORT, 0, 0)
+      expect(await getStatus(sim)).toBe(STATUS_IDLE)
+    } finally {
+      await sim.close()
+    }
+  })
+
+  test.concurrent(`${m.name} - abort is refused on a completed account`, async () => {
+    const sim = new Zemu(m.path)
+    try {
+      await sim.start(startOptions(m))
+      const app = new IronfishApp(sim.getTransport(), true)
+
+      await restoreAccount(m, sim, app, 'abort-completed')
+
+      await expect(sendCommand(sim, INS_DKG_ABORT, 0, 0)).rejects.toMatchObject({ statusCode: SW_INVALID_DKG_STATUS })
+      expect(await getStatus(sim)).toBe(STATUS_COMPLETED)
+    } finally {
+      await sim.close()
+    }
+  })
+})
@@ -0,0 +1,208 @@
+import { APP_SEED, defaultOptions, models, restoreKeysTestCases, SECOND_APP_SEED } from './common'
+import Zemu, { ButtonKind, IDeviceModel, isTouchDevice } from '@zondax/zemu'
+import { getResult, minimizeRound3Inputs, sendChunkedCommand, sendChunks, sendCommand, startTextFn } from './utils'
+import IronfishApp, { IronfishKeys } from '@zondax/ledger-ironfish'
+
+jest.setTimeout(450000)
+
+// Not covered by the js lib yet, which also restores to the first slot only
+const INS_DKG_RESTORE_KEYS = 0x1a
+const INS_DKG_GET_STATUS = 0x21
+const INS_DKG_BACKUP_KEYS_COSIGNERS = 0x23
+const INS_DKG_REENCRYPT_BACKUP = 0x24
+const INS_DKG_VERIFY_BACKUP = 0x25
+
+const STATUS_COMPLETED = 2
+
+const STORED_ACCOUNT_NONE = 0
+const STORED_ACCOUNT_SAME = 1
+const STORED_ACCOUNT_OTHER = 2
+
+// Seeding the rng makes the accounts, and every screen showing them, the same on every run
+const startOptions = (m: IDeviceModel, i: number, seed: string = APP_SEED) => ({
+  ...defaultOptions,
+  custom: `-s "${seed}" --deterministic-rng ${i + 1}`,
+  model: m.name,
+  startText: startTextFn(m.name),
+  approveKeyword: isTouchDevice(m.name) ? 'Approve' : '',
+  approveAction: ButtonKind.ApproveTapButton,
+})
+
+const approve = async (m: IDeviceModel, sim: Zemu, name: string, req: Promise<any>) => {
+  await sim.waitUntilScreenIsNot(sim.getMainMenuSnapshot())
+  await sim.compareSnapshotsAndApprove('.', `${m.prefix.toLowerCase()}-${name}`)
+  const resp = await req
+  await sim.deleteEvents()
+  return resp
+}
+
+// Runs a DKG among all the given sims, each one with its own identity index. Returns the public address of the account.
+const runDkg = async (m: IDeviceModel, sims: Zemu[], apps: IronfishApp[], name: string): Promise<string> => {
+  const participants = sims.length
+
+  const identities: string[] = []
+  for (let i = 0; i < participants; i++) {
+    const { identity } = await apps[i].dkgGetIdentity(i, false)
+    identities.push(identity.toString('hex'))
+  }
+
+  const round1s: { publicPackage: string; secretPackage: string }[] = []
+  for (let i = 0; i < participants; i++) {
+    const round1 = await approve(m, sims[i], `${name}-${i}-round1`, apps[i].dkgRound1(i, identities, participants))
+    round1s.push({ publicPackage: round1.publicPackage.toString('hex'), secretPackage: round1.secretPackage.toString('hex') })
+  }
+
+  const round2s: { publicPackage: string; secretPackage: string }[] = []
+  for (let i = 0; i < participants; i++) {
+    const round2Req = apps[i].dkgRound2(
+      i,
+      round1s.map(r => r.publicPackage),
+      round1s[i].secretPackage,
+    )
+    const round2 = await approve(m, sims[i], `${name}-${i}-round2`, round2Req)
+    round2s.push({ publicPackage: round2.publicPackage.toString('hex'), secretPackage: round2.secretPackage.toString('hex') })
+  }
+
+  for (let i = 0; i < participants; i++) {
+    const {
+      participants: ids,
+      round1PublicPkgs,
+      round2PublicPkgs,
+      gskBytes,
+    } = minimizeRound3Inputs(
+      i,
+      round1s.map(r => r.publicPackage),
+      round2s.filter((_, pos) => i != pos).map(r => r.publicPackage),
+    )
+    const round3Req = apps[i].dkgRound3Min(i, ids, round1PublicPkgs, round2PublicPkgs, round2s[i].secretPackage, gskBytes)
+    await approve(m, sims[i], `${name}-${i}-round3`, round3Req)
+  }
+
+  const { publicAddress } = await apps[0].dkgRetrieveKeys(IronfishKeys.PublicAddress)
+  return publicAddress.toString('hex')
+}
+
+describe.each(models)('backup', function (m) {
+  test.concurrent(`${m.name} - co-signers backup handed over to a replacement device`, async () => {
+    const sims = [new Zemu(m.path), new Zemu(m.path), new Zemu(m.path)]
+    try {
+      // The first two devices run the DKG, the third one replaces the first after losing its seed
+      for (let i = 0; i < sims.length; i++) await sims[i].start(startOptions(m, i, i == 2 ? SECOND_APP_SEED : APP_SEED))
+      const apps = sims.map(sim => new IronfishApp(sim.getTransport(), true))
+
+      const publicAddress = await runDkg(m, sims.slice(0, 2), apps.slice(0, 2), 'cosigners-dkg')
+
+      // Encrypt the keys of the first device to the second one
+      const { identity: cosigner } = await apps[1].dkgGetIdentity(1, false)
+      const { identities } = await apps[0].dkgGetIdentities()
+      const position = identities.findIndex((i: Buffer) => i.equals(cosigner))
+      expect(position).toBeGreaterThanOrEqual(0)
+
+      const backupReq = sendCommand(sims[0], INS_DKG_BACKUP_KEYS_COSIGNERS, 0, 0, Buffer.from([1, position]))
+      const chunks = await approve(m, sims[0], 'cosigners-backup', backupReq)
+      const backup = await getResult(sims[0], chunks[0])
+
+      // The replacement device can not open it until it is re-encrypted to it
+      await expect(apps[2].dkgRestoreKeys(backup.toString('hex'))).rejects.toThrow()
+
+      // The second device re-encrypts it to the replacement device
+      const { identity: replacement } = await apps[2].dkgGetIdentity(0, false)
+      const backupLen = Buffer.alloc(2)
+      backupLen.writeUInt16BE(backup.length)
+
+      const reencryptReq = sendChunkedCommand(sims[1], INS_DKG_REENCRYPT_BACKUP, 0, Buffer.concat([backupLen, backup, replacement]))
+      const reencrypted = await approve(m, sims[1], 'cosigners-reencrypt', reencryptReq)
+
+      // Which ends up with the same account as the first device
+      await approve(m, sims[2], 'cosigners-restore', apps[2].dkgRestoreKeys(reencrypted.toString('hex')))
+
+      const restored = await apps[2].dkgRetrieveKeys(IronfishKeys.PublicAddress)
+      expect(restored.publicAddress.toString('hex')).toEqual(publicAddress)
+
+      // The keys still belong to the first participant
+      const { identity: lost } = await apps[0].dkgGetIdentity(0, false)
+      const { identity } = await apps[2].dkgRetrieveKeys(IronfishKeys.DkgIdentity)
+      expect(identity.toString('hex')).toEqual(lost.toString('hex'))
+    } finally {
+      for (let i = 0; i < sims.length; i++) await sims[i].close()
+    }
+  })
+
+  test.concurrent(`${m.name} - verify a backup against the stored account`, async () => {
+    const sims = [new Zemu(m.path), new Zemu(m.path)]
+    try {
+      for (let i = 0; i < sims.length; i++) await sims[i].start(startOptions(m, i))
+      const apps = sims.map(sim => new IronfishApp(sim.getTransport(), true))
+
+      const publicAddress = await runDkg(m, sims, apps, 'verify-dkg')
+      const { encryptedKeys } = await approve(m, sims[0], 'verify-backup', apps[0].dkgBackupKeys())
+
+      const verify = async (name: string, slot: number, backup: Buffer) =>
+        approve(m, sims[0], name, sendChunks(sims[0], INS_DKG_VERIFY_BACKUP, slot, backup))
+
+      // Nothing is written to the slot, whatever the result is
+      let resp = await verify('verify-same', 0, encryptedKeys)
+      expect(resp[0]).toBe(STORED_ACCOUNT_SAME)
+      expect(resp.subarray(1, 3)).toEqual(Buffer.from([2, 2]))
+      expect(resp.subarray(3).toString('hex')).toEqual(publicAddress)
+
+      const { encrypted, publicAddress: otherAddress } = restoreKeysTestCases[0]
+      resp = await verify('verify-other', 0, Buffer.from(encrypted[0], 'hex'))
+      expect(resp[0]).toBe(STORED_ACCOUNT_OTHER)
+      expect(resp.subarray(3).toString('hex')).toEqual(otherAddress)
+
+      resp = await verify('verify-none', 1, encryptedKeys)
+      expect(resp[0]).toBe(STORED_ACCOUNT_NONE)
+      expect(resp.subarray(3).toString('hex')).toEqual(publicAddress)
+
+      const stored = await apps[0].dkgRetrieveKeys(IronfishKeys.PublicAddress)
+      expect(stored.publicAddress.toString('hex')).toEqual(publicAddress)
+    } finally {
+      for (let i = 0; i < sims.length; i++) await sims[i].close()
+    }
+  })
+
+  test.concurrent(`${m.name} - restore a v2 backup, and confirm before replacing another account`, async () => {
+    const sims = [new Zemu(m.path), new Zemu(m.path)]
+    try {
+      for (let i = 0; i < sims.length; i++) await sims[i].start(startOptions(m, i))
+      const apps = sims.map(sim => new IronfishApp(sim.getTransport(), true))
+
+      const publicAddress = await runDkg(m, sims, apps, 'restore-dkg')
+      const { encryptedKeys } = await approve(m, sims[0], 'restore-backup', apps[0].dkgBackupKeys())
+
+      // Backups carry a cleartext header describing the account: magic, version, public address, min signers and participants
+      expect(encryptedKeys.subarray(0, 5)).toEqual(Buffer.concat([Buffer.from('IFBK'), Buffer.from([2])]))
+      expect(encryptedKeys.subarray(5, 37).toString('hex')).toEqual(publicAddress)
+      expect(encryptedKeys.subarray(37, 39)).toEqual(Buffer.from([2, 2]))
+
+      // Round trip on an empty slot of the other device
+      await approve(m, sims[1], 'restore-v2', sendChunks(sims[1], INS_DKG_RESTORE_KEYS, 1, encryptedKeys))
+      expect(await sendCommand(sims[1], INS_DKG_GET_STATUS, 0, 1)).toEqual(Buffer.from([STATUS_COMPLETED, 0, 2, 2]))
+
+      const { encrypted, publicAddress: otherAddress } = restoreKeysTestCases[0]
+
+      // Replacing another account is refused unless confirmed
+      const rejectedReq = apps[0].dkgRestoreKeys(encrypted[0])
+      await sims[0].waitUntilScreenIsNot(sims[0].getMainMenuSnapshot())
+      await sims[0].compareSnapshotsAndReject('.', `${m.prefix.toLowerCase()}-restore-keep-account`)
+      await expect(rejectedReq).rejects.toThrow()
+
+      let stored = await apps[0].dkgRetrieveKeys(IronfishKeys.PublicAddress)
+      expect(stored.publicAddress.toString('hex')).toEqual(publicAddress)
+
+      // Once confirmed, the restore itself is reviewed
+      const restoreReq = apps[1].dkgRestoreKeys(encrypted[0])
+      await sims[1].waitUntilScreenIsNot(sims[1].getMainMenuSnapshot())
+      await sims[1].compareSnapshotsAndApprove('.', `${m.prefix.toLowerCase()}-restore-replace-account`)
+      await sims[1].waitForText('Restore Keys')
+      await sims[1].compareSnapshotsAndApprove('.', `${m.prefix.toLowerCase()}-restore-replace-restore`)
+      await restoreReq
+
+      stored = await apps[1].dkgRetrieveKeys(IronfishKeys.PublicAddress)
+      expect(stored.publicAddress.toString('hex')).toEqual(otherAddress)
+    } finally {
+      for (let i = 0; i < sims.length; i++) await sims[i].close()
+    }
+  })
+})
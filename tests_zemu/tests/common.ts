@@ -3,6 +3,9 @@ import { IDeviceModel, DEFAULT_START_OPTIONS } from '@zondax/zemu'
 import { resolve } from 'path'
 
 export const APP_SEED = 'equip will roof matter pink blind book anxiety banner elbow sun young'
+// Devices only hold identities 0 to 5, so participants beyond the sixth run on a device with another seed
+export const SECOND_APP_SEED = 'abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about'
+export const IDENTITIES_PER_SEED = 6
 
 // const APP_PATH_S = resolve('../app/target/nanos/release/ironfish-dkg')
 // const APP_PATH_X = resolve('../app/target/nanox/release/ironfish-dkg')
@@ -19,19 +19,32 @@ import { defaultOptions, models } from './common'
 import IronfishApp, { IronfishKeys } from '@zondax/ledger-ironfish'
 import { isValidPublicAddress, multisig, UnsignedTransaction, verifyTransactions } from '@ironfish/rust-nodejs'
 import { Transaction } from '@ironfish/sdk'
-import { buildTx, IronfishKeySet, minimizeRound3Inputs, runMethod, startTextFn } from './utils'
+import {
+  buildTx,
+  IronfishKeySet,
+  minimizeRound3Inputs,
+  participantIdentityIndex,
+  participantSeed,
+  runMethod,
+  sendChunkedCommand,
+  serializePackages,
+  startTextFn,
+} from './utils'
 import aggregateSignatureShares = multisig.aggregateSignatureShares
 
 jest.setTimeout(4500000)
 
 // ONE_GLOBAL_APP: Use this flag if the whole DKG process will run in only one app (all participants, all rounds). This takes precedence over ONE_APP_PER_PARTICIPANT.
-// ONE_APP_PER_PARTICIPANT: Use this flag if the whole DKG process will run in one app per participant
+// ONE_APP_PER_PARTICIPANT: Use this flag if the whole DKG process will run in one app per participant. Required for more than 6 participants.
 // Otherwise, if both are falsy, one app will be started per request (each round for each participant)
 const ONE_GLOBAL_APP = 0
 const ONE_APP_PER_PARTICIPANT = 1
 
 const SKIP_ERRORS_IN_PHASE = true
 
+// Not covered by the js lib yet
+const INS_DKG_ROUND_3_FULL = 0x1e
+
 // Reference taken from https://github.com/iron-fish/ironfish/pull/5324/files
 
 describe.each(models)('DKG', function (m) {
@@ -45,6 +58,8 @@ describe.each(models)('DKG', function (m) {
   })
 
   describe.each([
+    { p: 7, min: 4 },
+    { p: 5, min: 3 },
     { p: 4, min: 2 },
     { p: 3, min: 2 },
     { p: 2, min: 2 },
@@ -59,6 +74,8 @@ describe.each(models)('DKG', function (m) {
         let sim = globalSims[i]
         await sim.start({
           ...defaultOptions,
+          // Seeding the rng makes the group key, and every screen showing it, the same on every run
+          custom: `-s "${participantSeed(i)}" --deterministic-rng ${i + 1}`,
           model: m.name,
           startText: startTextFn(m.name),
           approveKeyword: isTouchDevice(m.name) ? 'Approve' : '',
@@ -83,7 +100,7 @@ describe.each(models)('DKG', function (m) {
         for (let i = 0; i < participants; i++) {
           try {
             const identity = await runMethod(m, globalSims, i, async (sim: Zemu, app: IronfishApp) => {
-              const result = await app.dkgGetIdentity(i, false)
+              const result = await app.dkgGetIdentity(participantIdentityIndex(i), false)
 
               expect(result.identity.length).toBeTruthy()
               return result
@@ -100,7 +117,7 @@ describe.each(models)('DKG', function (m) {
         for (let i = 0; i < participants; i++) {
           try {
             const round1 = await runMethod(m, globalSims, i, async (sim: Zemu, app: IronfishApp) => {
-              const round1Req = app.dkgRound1(i, identities, minSigners)
+              const round1Req = app.dkgRound1(participantIdentityIndex(i), identities, minSigners)
 
               await sim.waitUntilScreenIsNot(sim.getMainMenuSnapshot())
               await sim.compareSnapshotsAndApprove('.', `${m.prefix.toLowerCase()}-dkg-p${participants}-m${minSigners}-${i}-round1`)
@@ -124,7 +141,7 @@ describe.each(models)('DKG', function (m) {
           try {
             const round2 = await runMethod(m, globalSims, i, async (sim: Zemu, app: IronfishApp) => {
               const round2Req = app.dkgRound2(
-                i,
+                participantIdentityIndex(i),
                 round1s.map(r => r.publicPackage),
                 round1s[i].secretPackage,
               )
@@ -161,7 +178,14 @@ describe.each(models)('DKG', function (m) {
                 round2s.filter((_, pos) => i != pos).map(r => r.publicPackage),
               )
 
-              let round3Req = app.dkgRound3Min(i, ids, round1PublicPkgs, round2PublicPkgs, round2s[i].secretPackage, gskBytes)
+              let round3Req = app.dkgRound3Min(
+                participantIdentityIndex(i),
+                ids,
+                round1PublicPkgs,
+                round2PublicPkgs,
+                round2s[i].secretPackage,
+                gskBytes,
+              )
 
               await sim.waitUntilScreenIsNot(sim.getMainMenuSnapshot())
               await sim.compareSnapshotsAndApprove('.', `${m.prefix.toLowerCase()}-dkg-p${participants}-m${minSigners}-${i}-round3`)
@@ -192,12 +216,7 @@ describe.each(models)('DKG', function (m) {
               let resultReq = app.dkgBackupKeys()
 
               await sim.waitUntilScreenIsNot(sim.getMainMenuSnapshot())
-              try {
-                await sim.compareSnapshotsAndApprove('.', `${m.prefix.toLowerCase()}-tmp-dkg-p${participants}-m${minSigners}-${i}-backup`)
-              } catch (e) {
-                // TODO navigate and approve, but do not compare snapshots... needs to be added to zemu
-                // Skip error, as a new public address is generated each time. Snapshots will be different in every run
-              }
+              await sim.compareSnapshotsAndApprove('.', `${m.prefix.toLowerCase()}-dkg-p${participants}-m${minSigners}-${i}-backup`)
 
               const result = await resultReq
 
@@ -347,12 +366,16 @@ describe.each(models)('DKG', function (m) {
 
         for (let i = 0; i < participants; i++) {
           const result = await runMethod(m, globalSims, i, async (sim: Zemu, app: IronfishApp) => {
-            let result = await app.dkgSign(
+            const resultReq = app.dkgSign(
               unsignedTx.publicKeyRandomness(),
               signingPackage.frostSigningPackage().toString('hex'),
               unsignedTx.hash().toString('hex'),
             )
 
+            await sim.waitUntilScreenIsNot(sim.getMainMenuSnapshot())
+            await sim.compareSnapshotsAndApprove('.', `${m.prefix.toLowerCase()}-dkg-sign-p${participants}-m${minSigners}-${i}-signers`)
+
+            const result = await resultReq
             expect(result.signature.length).toBeTruthy()
 
             return result
@@ -375,4 +398,126 @@ describe.each(models)('DKG', function (m) {
       }
     })
   })
+
+  // Even participants run the full version of round 3, odd ones the min version. All of them must end up with the same account.
+  it.concurrent(`${m.name} - full and min round 3 at max participants`, async function () {
+    const participants = 7
+    const minSigners = 4
+    const globalSims: Zemu[] = []
+    for (let i = 0; i < participants; i++) globalSims.push(new Zemu(m.path))
+
+    try {
+      for (let i = 0; i < participants; i++) {
+        await globalSims[i].start({
+          ...defaultOptions,
+          custom: `-s "${participantSeed(i)}" --deterministic-rng ${i + 1}`,
+          model: m.name,
+          startText: startTextFn(m.name),
+          approveKeyword: isTouchDevice(m.name) ? 'Approve' : '',
+          approveAction: ButtonKind.ApproveTapButton,
+        })
+        await globalSims[i].toggleExpertMode()
+      }
+
+      const identities: string[] = []
+      for (let i = 0; i < participants; i++) {
+        const result = await runMethod(m, globalSims, i, async (_sim: Zemu, app: IronfishApp) =>
+          app.dkgGetIdentity(participantIdentityIndex(i), false),
+        )
+        identities.push(result.identity.toString('hex'))
+      }
+
+      const round1s: { publicPackage: string; secretPackage: string }[] = []
+      for (let i = 0; i < participants; i++) {
+        const round1 = await runMethod(m, globalSims, i, async (sim: Zemu, app: IronfishApp) => {
+          const round1Req = app.dkgRound1(participantIdentityIndex(i), identities, minSigners)
+          await sim.waitUntilScreenIsNot(sim.getMainMenuSnapshot())
+          await sim.compareSnapshotsAndApprove('.', `${m.prefix.toLowerCase()}-dkg-full-p${participants}-m${minSigners}-${i}-round1`)
+          return round1Req
+        })
+        round1s.push({ publicPackage: round1.publicPackage.toString('hex'), secretPackage: round1.secretPackage.toString('hex') })
+      }
+
+      const round2s: { publicPackage: string; secretPackage: string }[] = []
+      for (let i = 0; i < participants; i++) {
+        const round2 = await runMethod(m, globalSims, i, async (sim: Zemu, app: IronfishApp) => {
+          const round2Req = app.dkgRound2(
+            participantIdentityIndex(i),
+            round1s.map(r => r.publicPackage),
+            round1s[i].secretPackage,
+          )
+          await sim.waitUntilScreenIsNot(sim.getMainMenuSnapshot())
+          await sim.compareSnapshotsAndApprove('.', `${m.prefix.toLowerCase()}-dkg-full-p${participants}-m${minSigners}-${i}-round2`)
+          return round2Req
+        })
+        round2s.push({ publicPackage: round2.publicPackage.toString('hex'), secretPackage: round2.secretPackage.toString('hex') })
+      }
+
+      for (let i = 0; i < participants; i++) {
+        await runMethod(m, globalSims, i, async (sim: Zemu, app: IronfishApp) => {
+          let round3Req: Promise<any>
+          if (i % 2 === 0) {
+            const secretPackage = Buffer.from(round2s[i].secretPackage, 'hex')
+            const secretPackageLen = Buffer.alloc(2)
+            secretPackageLen.writeUInt16BE(secretPackage.length)
+
+            round3Req = sendChunkedCommand(
+              sim,
+              INS_DKG_ROUND_3_FULL,
+              0,
+              Buffer.concat([
+                Buffer.from([participantIdentityIndex(i)]),
+                serializePackages(round1s.map(r => r.publicPackage)),
+                serializePackages(round2s.map(r => r.publicPackage)),
+                secretPackageLen,
+                secretPackage,
+              ]),
+            )
+          } else {
+            const {
+              participants: ids,
+              round1PublicPkgs,
+              round2PublicPkgs,
+              gskBytes,
+            } = minimizeRound3Inputs(
+              i,
+              round1s.map(r => r.publicPackage),
+              round2s.filter((_, pos) => i != pos).map(r => r.publicPackage),
+            )
+            round3Req = app.dkgRound3Min(
+              participantIdentityIndex(i),
+              ids,
+              round1PublicPkgs,
+              round2PublicPkgs,
+              round2s[i].secretPackage,
+              gskBytes,
+            )
+          }
+
+          await sim.waitUntilScreenIsNot(sim.getMainMenuSnapshot())
+          await sim.compareSnapshotsAndApprove('.', `${m.prefix.toLowerCase()}-dkg-full-p${participants}-m${minSigners}-${i}-round3`)
+          return round3Req
+        })
+      }
+
+      const publicPackages: string[] = []
+      const pks: string[] = []
+      for (let i = 0; i < participants; i++) {
+        await runMethod(m, globalSims, i, async (_sim: Zemu, app: IronfishApp) => {
+          const { publicPackage } = await app.dkgGetPublicPackage()
+          const { publicAddress } = await app.dkgRetrieveKeys(IronfishKeys.PublicAddress)
+          if (!publicPackage || !publicAddress) throw new Error('no public package or public address found')
+
+          publicPackages.push(publicPackage.toString('hex'))
+          pks.push(publicAddress.toString('hex'))
+        })
+      }
+
+      expect(new Set(publicPackages).size).toBe(1)
+      expect(new Set(pks).size).toBe(1)
+      expect(isValidPublicAddress(pks[0])).toBeTruthy()
+    } finally {
+      for (let i = 0; i < globalSims.length; i++) await globalSims[i].close()
+    }
+  })
 })
@@ -1,8 +1,8 @@
 import { defaultOptions, identities, models, restoreKeysTestCases } from './common'
-import Zemu, { ButtonKind, isTouchDevice } from '@zondax/zemu'
-import { buildTx, IronfishKeySet, runMethod, startTextFn } from './utils'
+import Zemu, { ButtonKind, IDeviceModel, isTouchDevice } from '@zondax/zemu'
+import { buildTx, IronfishKeySet, runMethod, sendChunkedCommand, startTextFn } from './utils'
 import IronfishApp, { IronfishKeys } from '@zondax/ledger-ironfish'
-import { UnsignedTransaction } from '@ironfish/rust-nodejs'
+import { multisig, UnsignedTransaction } from '@ironfish/rust-nodejs'
 
 jest.setTimeout(450000)
 
@@ -12,6 +12,107 @@ jest.setTimeout(450000)
 const ONE_GLOBAL_APP = 0
 const ONE_APP_PER_PARTICIPANT = 1
 
+// Signing is sent directly to the app, so the status returned can be checked
+const INS_DKG_SIGN = 0x15
+
+const SW_INVALID_RANDOMIZER = 0xb012
+const SW_INVALID_SIGNING_MESSAGE = 0xb02a
+const SW_INVALID_SIGNING_COMMITMENT = 0xb02b
+const SW_NONCE_REUSE = 0xb02c
+
+const approve = async (m: IDeviceModel, sim: Zemu, name: string, req: Promise<any>) => {
+  await sim.waitUntilScreenIsNot(sim.getMainMenuSnapshot())
+  await sim.compareSnapshotsAndApprove('.', `${m.prefix.toLowerCase()}-${name}`)
+  const resp = await req
+  await sim.deleteEvents()
+  return resp
+}
+
+// Every sign attempt clears the reviewed tx, so it has to be reviewed again before the next one
+const reviewTx = async (m: IDeviceModel, sim: Zemu, index: number, unsignedTx: UnsignedTransaction) => {
+  const app = new IronfishApp(sim.getTransport(), true)
+  const reviewReq = app.reviewTransaction(unsignedTx.serialize().toString('hex'))
+  const { hash } = await approve(m, sim, `dkg-sign-${index}-review-transaction`, reviewReq)
+  expect(hash.toString('hex')).toBe(unsignedTx.hash().toString('hex'))
+}
+
+// Same payload the js lib sends on dkgSign: randomizer and frost signing package (both u16 be len prefixed), and the tx hash
+const sign = (sim: Zemu, randomness: string, frostSigningPackage: Buffer, hash: Buffer) => {
+  const lenOf = (data: Buffer) => {
+    const len = Buffer.alloc(2)
+    len.writeUInt16BE(data.length)
+    return len
+  }
+  const randomizer = Buffer.from(randomness, 'hex')
+
+  return sendChunkedCommand(
+    sim,
+    INS_DKG_SIGN,
+    0,
+    Buffer.concat([lenOf(randomizer), randomizer, lenOf(frostSigningPackage), frostSigningPackage, hash]),
+  )
+}
+
+// Builds the frost signing package of the tx out of the raw commitments of each participant
+const frostSigningPackage = (unsignedTx: UnsignedTransaction, identities: string[], rawCommitments: Buffer[]) => {
+  const commitments = rawCommitments.map((raw, i) =>
+    multisig.SigningCommitment.fromRaw(identities[i], raw, unsignedTx.hash(), identities).serialize().toString('hex'),
+  )
+  const signingPackage = new multisig.SigningPackage(Buffer.from(unsignedTx.signingPackage(commitments), 'hex'))
+
+  return signingPackage.frostSigningPackage()
+}
+
+// Restores the test case account on one sim per participant, and gets every one of them
+// to review the same tx and hand over their commitments for it
+const prepareSigning = async (m: IDeviceModel, sims: Zemu[], index: number, encrypted: string[]) => {
+  const identities: string[] = []
+  const rawCommitments: Buffer[] = []
+
+  for (let i = 0; i < sims.length; i++) {
+    await sims[i].start({
+      ...defaultOptions,
+      model: m.name,
+      startText: startTextFn(m.name),
+      approveKeyword: isTouchDevice(m.name) ? 'Approve' : '',
+      approveAction: ButtonKind.ApproveTapButton,
+    })
+    await sims[i].toggleExpertMode()
+
+    const app = new IronfishApp(sims[i].getTransport(), true)
+    await approve(m, sims[i], `dkg-sign-${index}-restore-keys`, app.dkgRestoreKeys(encrypted[i]))
+
+    const { identity } = await app.dkgRetrieveKeys(IronfishKeys.DkgIdentity)
+    if (!identity) throw new Error('no identity found')
+    identities.push(identity.toString('hex'))
+
+    // Change the approve button type to hold, as we are signing a tx now.
+    sims[i].startOptions.approveAction = ButtonKind.ApproveHoldButton
+  }
+
+  const app = new IronfishApp(sims[0].getTransport(), true)
+  const viewKey: any = await app.dkgRetrieveKeys(IronfishKeys.ViewKey)
+  const proofKey: any = await app.dkgRetrieveKeys(IronfishKeys.ProofGenerationKey)
+  const pubkey: any = await app.dkgRetrieveKeys(IronfishKeys.PublicAddress)
+
+  const senderKey: IronfishKeySet = {
+    publicAddress: pubkey.publicAddress.toString('hex'),
+    viewKey: { viewKey: viewKey.viewKey.toString('hex'), ivk: viewKey.ivk.toString('hex'), ovk: viewKey.ovk.toString('hex') },
+    proofKey: { ak: proofKey.ak.toString('hex'), nsk: proofKey.nsk.toString('hex') },
+  }
+  const unsignedTx = new UnsignedTransaction(buildTx(senderKey))
+
+  for (let i = 0; i < sims.length; i++) {
+    await reviewTx(m, sims[i], index, unsignedTx)
+
+    const app = new IronfishApp(sims[i].getTransport(), true)
+    const { commitments } = await app.dkgGetCommitments(unsignedTx.hash().toString('hex'))
+    rawCommitments.push(commitments)
+  }
+
+  return { senderKey, unsignedTx, identities, rawCommitments }
+}
+
 describe.each(models)('wrong actions', function (m) {
   describe.each(restoreKeysTestCases)(`${m.name} - attempt to sign after sending wrong command`, ({ index, encrypted }) => {
     test(index + '', async () => {
@@ -249,6 +350,80 @@ describe.each(models)('wrong actions', function (m) {
     })
   })
 
+  describe.each(restoreKeysTestCases)(`${m.name} - attempt to sign a package not matching the reviewed tx`, ({ index, encrypted }) => {
+    test(index + '', async () => {
+      const sims = encrypted.map(() => new Zemu(m.path))
+      try {
+        const { senderKey, unsignedTx, identities, rawCommitments } = await prepareSigning(m, sims, index, encrypted)
+        const randomness = unsignedTx.publicKeyRandomness()
+
+        // The signing package is for another tx, while the reviewed hash is sent along with it
+        const otherTx = new UnsignedTransaction(buildTx(senderKey))
+        await expect(
+          sign(sims[0], randomness, frostSigningPackage(otherTx, identities, rawCommitments), unsignedTx.hash()),
+        ).rejects.toMatchObject({ statusCode: SW_INVALID_SIGNING_MESSAGE })
+
+        // Our own commitment is swapped for the one of a co-signer
+        await reviewTx(m, sims[0], index, unsignedTx)
+        const swapped = [rawCommitments[1], ...rawCommitments.slice(1)]
+        await expect(
+          sign(sims[0], randomness, frostSigningPackage(unsignedTx, identities, swapped), unsignedTx.hash()),
+        ).rejects.toMatchObject({ statusCode: SW_INVALID_SIGNING_COMMITMENT })
+      } finally {
+        for (let i = 0; i < sims.length; i++) await sims[i].close()
+      }
+    })
+  })
+
+  describe.each(restoreKeysTestCases)(`${m.name} - attempt to sign with a randomizer not from the reviewed tx`, ({ index, encrypted }) => {
+    test(index + '', async () => {
+      const sims = encrypted.map(() => new Zemu(m.path))
+      try {
+        const { senderKey, unsignedTx, identities, rawCommitments } = await prepareSigning(m, sims, index, encrypted)
+
+        // Everything else matches the reviewed tx
+        const otherTx = new UnsignedTransaction(buildTx(senderKey))
+        await expect(
+          sign(sims[0], otherTx.publicKeyRandomness(), frostSigningPackage(unsignedTx, identities, rawCommitments), unsignedTx.hash()),
+        ).rejects.toMatchObject({ statusCode: SW_INVALID_RANDOMIZER })
+      } finally {
+        for (let i = 0; i < sims.length; i++) await sims[i].close()
+      }
+    })
+  })
+
+  describe.each(restoreKeysTestCases)(`${m.name} - attempt to sign the same tx twice with other co-signers`, ({ index, encrypted }) => {
+    test(index + '', async () => {
+      const sims = encrypted.map(() => new Zemu(m.path))
+      try {
+        const { unsignedTx, identities, rawCommitments } = await prepareSigning(m, sims, index, encrypted)
+        const randomness = unsignedTx.publicKeyRandomness()
+        const signingPackage = frostSigningPackage(unsignedTx, identities, rawCommitments)
+
+        const signReq = sign(sims[0], randomness, signingPackage, unsignedTx.hash())
+        const signature = await approve(m, sims[0], `dkg-sign-${index}-history-signers`, signReq)
+        expect(signature.length).toBeTruthy()
+
+        // Signing the very same package again is fine, and gives back the same share
+        await reviewTx(m, sims[0], index, unsignedTx)
+        const againReq = sign(sims[0], randomness, signingPackage, unsignedTx.hash())
+        const again = await approve(m, sims[0], `dkg-sign-${index}-history-signers`, againReq)
+        expect(again).toEqual(signature)
+
+        // Min signers is 2, so our commitment along with the first co-signer one is a valid package.
+        // Our nonces only depend on the tx hash though, so signing it would reuse them.
+        await reviewTx(m, sims[0], index, unsignedTx)
+        const fewerSigners = frostSigningPackage(unsignedTx, identities.slice(0, 2), rawCommitments.slice(0, 2))
+        const reuseReq = sign(sims[0], randomness, fewerSigners, unsignedTx.hash())
+        await expect(approve(m, sims[0], `dkg-sign-${index}-history-fewer-signers`, reuseReq)).rejects.toMatchObject({
+          statusCode: SW_NONCE_REUSE,
+        })
+      } finally {
+        for (let i = 0; i < sims.length; i++) await sims[i].close()
+      }
+    })
+  })
+
   test.concurrent(`${m.name} - attempt to retrieve viewKeys when no keys are present`, async () => {
     const sim = new Zemu(m.path)
     try {
@@ -365,7 +540,7 @@ describe.each(models)('wrong actions', function (m) {
     }
   })
 
-  test.concurrent(`${m.name} - attempt to run round1 with 5 participants`, async () => {
+  test.concurrent(`${m.name} - attempt to run round1 with more than 7 participants`, async () => {
     const sim = new Zemu(m.path)
     try {
       await sim.start({
@@ -380,7 +555,11 @@ describe.each(models)('wrong actions', function (m) {
       await expect(
         app.dkgRound1(
           0,
-          identities.map(({ v }) => v),
+          [
+            ...identities.map(({ v }) => v),
+            multisig.ParticipantSecret.random().toIdentity().serialize().toString('hex'),
+            multisig.ParticipantSecret.random().toIdentity().serialize().toString('hex'),
+          ],
           3,
         ),
       ).rejects.toThrow()
@@ -389,6 +568,32 @@ describe.each(models)('wrong actions', function (m) {
     }
   })
 
+  test.concurrent(`${m.name} - attempt to run round1 with inconsistent participants`, async () => {
+    const sim = new Zemu(m.path)
+    try {
+      await sim.start({
+        ...defaultOptions,
+        model: m.name,
+        startText: startTextFn(m.name),
+        approveKeyword: isTouchDevice(m.name) ? 'Approve' : '',
+        approveAction: ButtonKind.ApproveTapButton,
+      })
+      const app = new IronfishApp(sim.getTransport(), true)
+      const ids = identities.map(({ v }) => v)
+
+      // Our own identity (index 0) is not part of the participants
+      await expect(app.dkgRound1(0, ids.slice(1), 2)).rejects.toThrow()
+
+      // Repeated identities
+      await expect(app.dkgRound1(0, [ids[0], ids[0], ids[1]], 2)).rejects.toThrow()
+
+      // More min signers than participants
+      await expect(app.dkgRound1(0, ids.slice(0, 3), 4)).rejects.toThrow()
+    } finally {
+      await sim.close()
+    }
+  })
+
   // TODO complete me
   /*
   test.concurrent(`${m.name} - attempt to run round3 when no round1 was executed`, async () => {
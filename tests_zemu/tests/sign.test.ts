@@ -1,6 +1,6 @@
 import { defaultOptions, models, restoreKeysTestCases } from './common'
 import Zemu, { ButtonKind, isTouchDevice } from '@zondax/zemu'
-import { buildTx, IronfishKeySet, runMethod, startTextFn } from './utils'
+import { buildTx, IronfishKeySet, runMethod, sendChunkedCommand, sendChunks, startTextFn } from './utils'
 import IronfishApp, { IronfishKeys } from '@zondax/ledger-ironfish'
 import { multisig, UnsignedTransaction, verifyTransactions } from '@ironfish/rust-nodejs'
 import { Transaction } from '@ironfish/sdk'
@@ -14,6 +14,30 @@ jest.setTimeout(450000)
 const ONE_GLOBAL_APP = 0
 const ONE_APP_PER_PARTICIPANT = 1
 
+// Not covered by the js lib yet
+const INS_DKG_AGGREGATE = 0x1d
+
+const SW_INVALID_PARTICIPANT_SHARE = 0xb02e
+
+// Each frost signature share goes along with the position of its owner in the DKG identities
+const serializeAggregate = (randomness: string, frostSigningPackage: Buffer, shares: [number, Buffer][]) => {
+  const lenOf = (data: Buffer) => {
+    const len = Buffer.alloc(2)
+    len.writeUInt16BE(data.length)
+    return len
+  }
+  const randomizer = Buffer.from(randomness, 'hex')
+
+  return Buffer.concat([
+    lenOf(randomizer),
+    randomizer,
+    lenOf(frostSigningPackage),
+    frostSigningPackage,
+    Buffer.from([shares.length]),
+    ...shares.map(([index, share]) => Buffer.concat([Buffer.from([index]), lenOf(share), share])),
+  ])
+}
+
 const TEST_OUTPUT_KEY: IronfishKeySet = {
   publicAddress: '87318a66842817fcc22001782eced854259133792e8a7d492689384bc6933683',
   viewKey: {
@@ -37,6 +61,7 @@ describe.each(models)('review transaction', function (m) {
       let identities: any[] = []
       let commitments: any[] = []
       let signatures: any[] = []
+      let frostShares: Buffer[] = []
 
       if (ONE_GLOBAL_APP) globalSims.push(new Zemu(m.path))
       else if (ONE_APP_PER_PARTICIPANT) for (let i = 0; i < participants; i++) globalSims.push(new Zemu(m.path))
@@ -151,12 +176,16 @@ describe.each(models)('review transaction', function (m) {
 
         for (let i = 0; i < participants; i++) {
           const result = await runMethod(m, globalSims, i, async (sim: Zemu, app: IronfishApp) => {
-            let result = await app.dkgSign(
+            const resultReq = app.dkgSign(
               unsignedTx.publicKeyRandomness(),
               signingPackage.frostSigningPackage().toString('hex'),
               unsignedTx.hash().toString('hex'),
             )
 
+            await sim.waitUntilScreenIsNot(sim.getMainMenuSnapshot())
+            await sim.compareSnapshotsAndApprove('.', `${m.prefix.toLowerCase()}-dkg-sign-${index}-${i}-signers`)
+
+            const result = await resultReq
             expect(result.signature.length).toBeTruthy()
 
             return result
@@ -165,6 +194,7 @@ describe.each(models)('review transaction', function (m) {
           signatures.push(
             multisig.SignatureShare.fromFrost(result.signature, Buffer.from(identities[i], 'hex')).serialize().toString('hex'),
           )
+          frostShares.push(result.signature)
         }
 
         // Attempt to sign again. It should fail as the tx hash is cleaned
@@ -187,6 +217,33 @@ describe.each(models)('review transaction', function (m) {
         expect(signedTx.spends.length).toBe(1)
         expect(signedTx.mints.length).toBe(1)
         expect(signedTx.burns.length).toBe(0)
+
+        // The device aggregates the very same signature the js lib puts on the tx
+        const { identities: storedIdentities } = await runMethod(m, globalSims, 0, async (sim: Zemu, app: IronfishApp) => {
+          return await app.dkgGetIdentities()
+        })
+        const shares: [number, Buffer][] = frostShares.map((share, i) => [
+          storedIdentities.findIndex((identity: Buffer) => identity.toString('hex') === identities[i]),
+          share,
+        ])
+        const randomness = unsignedTx.publicKeyRandomness()
+        const frostSigningPackage = signingPackage.frostSigningPackage()
+
+        const signature = await runMethod(m, globalSims, 0, async (sim: Zemu) => {
+          return await sendChunkedCommand(sim, INS_DKG_AGGREGATE, 0, serializeAggregate(randomness, frostSigningPackage, shares))
+        })
+        expect(signature.length).toBe(64)
+        expect(Buffer.from(signedTxRaw).includes(signature)).toBeTruthy()
+
+        // A share sent for the wrong participant is refused, and that participant is reported
+        const [faultyIndex] = shares[0]
+        const corrupted: [number, Buffer][] = [[faultyIndex, shares[1][1]], ...shares.slice(1)]
+
+        const faulty = await runMethod(m, globalSims, 0, async (sim: Zemu) => {
+          const payload = serializeAggregate(randomness, frostSigningPackage, corrupted)
+          return await sendChunks(sim, INS_DKG_AGGREGATE, 0, payload, [SW_INVALID_PARTICIPANT_SHARE])
+        })
+        expect(faulty).toEqual(Buffer.from([faultyIndex]))
       } finally {
         for (let i = 0; i < globalSims.length; i++) await globalSims[i].close()
       }
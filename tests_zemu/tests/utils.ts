@@ -4,7 +4,7 @@ import { devUtils, Note as NoteSDK } from '@ironfish/sdk'
 import { TModel } from '@zondax/zemu/dist/types'
 import Zemu, { ButtonKind, DEFAULT_START_OPTIONS, IDeviceModel, isTouchDevice } from '@zondax/zemu'
 import IronfishApp from '@zondax/ledger-ironfish'
-import { defaultOptions } from './common'
+import { APP_SEED, defaultOptions, IDENTITIES_PER_SEED, SECOND_APP_SEED } from './common'
 
 export interface IronfishKeySet {
   publicAddress: string
@@ -112,6 +112,79 @@ export const minimizeRound3Inputs = (index: number, round1PublicPackages: string
   }
 }
 
+// Identity index and seed used by the device of each participant, when running one app per participant
+export const participantIdentityIndex = (i: number) => i % IDENTITIES_PER_SEED
+export const participantSeed = (i: number) => (i < IDENTITIES_PER_SEED ? APP_SEED : SECOND_APP_SEED)
+
+const CLA = 0x63
+const INS_GET_RESULT = 0x1b
+const CHUNK_SIZE = 250
+const PATH_LEN = 20
+const SW_OK = 0x9000
+
+// Helpers below talk to the app directly, for instructions the js lib does not cover yet.
+// Responses are returned without the status word. Any status other than 0x9000 is thrown by the transport.
+
+// Sends a single APDU command, only accepting the given status words
+export const sendCommand = async (
+  sim: Zemu,
+  ins: number,
+  p1: number,
+  slot: number,
+  data: Buffer = Buffer.alloc(0),
+  statusList: number[] = [SW_OK],
+): Promise<Buffer> => {
+  const resp = await sim.getTransport().send(CLA, ins, p1, slot, data, statusList)
+  return resp.subarray(0, resp.length - 2)
+}
+
+// Sends a command following the chunk protocol, and returns the response to the last chunk.
+// The first chunk carries the derivation path, which these instructions ignore.
+// The status words given are the ones accepted on the last chunk, so error responses carrying data can be read.
+export const sendChunks = async (
+  sim: Zemu,
+  ins: number,
+  slot: number,
+  payload: Buffer,
+  statusList: number[] = [SW_OK],
+): Promise<Buffer> => {
+  const chunks = [Buffer.alloc(PATH_LEN)]
+  for (let i = 0; i < payload.length; i += CHUNK_SIZE) chunks.push(payload.subarray(i, i + CHUNK_SIZE))
+
+  let resp = Buffer.alloc(0)
+  for (let i = 0; i < chunks.length; i++) {
+    const last = i === chunks.length - 1
+    const p1 = i === 0 ? 0 : last ? 2 : 1
+    resp = await sendCommand(sim, ins, p1, slot, chunks[i], last ? statusList : [SW_OK])
+  }
+
+  return resp
+}
+
+// Fetches the result saved by the last command, given the amount of chunks it reported
+export const getResult = async (sim: Zemu, chunks: number): Promise<Buffer> => {
+  let result = Buffer.alloc(0)
+  for (let page = 0; page < chunks; page++) {
+    result = Buffer.concat([result, await sendCommand(sim, INS_GET_RESULT, page, 0)])
+  }
+
+  return result
+}
+
+// Sends a command following the chunk protocol, and retrieves its result
+export const sendChunkedCommand = async (sim: Zemu, ins: number, slot: number, payload: Buffer): Promise<Buffer> => {
+  const resp = await sendChunks(sim, ins, slot, payload)
+  return getResult(sim, resp[0])
+}
+
+// Serializes a set of packages with the same length, as a qty (u8), the length of each one (u16 be) and the packages
+export const serializePackages = (packages: string[]): Buffer => {
+  const header = Buffer.alloc(3)
+  header.writeUInt8(packages.length, 0)
+  header.writeUInt16BE(packages.length ? packages[0].length / 2 : 0, 1)
+  return Buffer.concat([header, ...packages.map(p => Buffer.from(p, 'hex'))])
+}
+
 // Not sure about the start text for flex and stax, so we go with what it always work, which is the app name.
 // That is always displayed on the main menu
 export const startTextFn = (model: TModel) => (isTouchDevice(model) ? 'Ironfish DKG' : DEFAULT_START_OPTIONS.startText)